@@ -0,0 +1,118 @@
+use alloc::fmt::Debug;
+use core::ops::Deref;
+
+use crate::{Delegate, Response, Subscription};
+
+/// Observable which does not own its value, but holds an exclusive reference to it instead.
+///
+/// This makes it possible to observe mutations of a value owned by another struct.
+/// The value cannot be accessed other than through the borrowed observable for as long
+/// as the borrowed observable exists, and callbacks cannot outlive the borrowed value.
+///
+/// ``` rust
+/// use squeak::{BorrowedObservable, Response};
+///
+/// struct Character {
+///     health: u32,
+/// }
+///
+/// let mut character = Character { health: 100 };
+/// {
+///     let mut health = BorrowedObservable::new(&mut character.health);
+///     health.subscribe(|updated_health| {
+///         println!("Health is now {updated_health}");
+///         Response::StaySubscribed
+///     });
+///     health.mutate(|h| *h -= 10); // Prints "Health is now 90"
+/// }
+/// assert_eq!(character.health, 90);
+/// ```
+#[derive(Debug)]
+pub struct BorrowedObservable<'a, T> {
+    value: &'a mut T,
+    delegate: Delegate<'a, T>,
+}
+
+impl<'a, T> BorrowedObservable<'a, T> {
+    /// Creates a new observable wrapping an existing value.
+    ///
+    /// ```rust
+    /// use squeak::BorrowedObservable;
+    /// let mut name = String::from("DefaultName");
+    /// let name = BorrowedObservable::new(&mut name);
+    /// ```
+    pub fn new(value: &'a mut T) -> Self {
+        Self {
+            value,
+            delegate: Delegate::new(),
+        }
+    }
+
+    /// Registers a new callback that will be called when the borrowed value is mutated.
+    ///
+    /// ```rust
+    /// use squeak::{BorrowedObservable, Response};
+    ///
+    /// let mut health = 100;
+    /// let health = BorrowedObservable::new(&mut health);
+    /// health.subscribe(|updated_health| {
+    ///     println!("Health is now {updated_health}");
+    ///     Response::StaySubscribed
+    /// });
+    /// ```
+    pub fn subscribe<C: FnMut(&T) -> Response + 'a + Send>(&self, callback: C) -> Subscription {
+        self.delegate.subscribe(callback)
+    }
+
+    /// Removes a callback that was previously registered.
+    ///
+    /// ```rust
+    /// use squeak::{BorrowedObservable, Response};
+    ///
+    /// let mut health = 100;
+    /// let health = BorrowedObservable::new(&mut health);
+    /// let subscription = health.subscribe(|updated_health| {
+    ///     println!("Health is now {updated_health}");
+    ///     Response::StaySubscribed
+    /// });
+    /// health.unsubscribe(subscription);
+    /// ```
+    pub fn unsubscribe(&self, subscription: Subscription) {
+        self.delegate.unsubscribe(subscription);
+    }
+
+    /// Returns a reference to a delegate that will execute subscription functions
+    /// when the borrowed value is mutated.
+    pub fn delegate(&self) -> &Delegate<'a, T> {
+        &self.delegate
+    }
+
+    /// Execute a function which may mutate the borrowed value.
+    /// Subscription callbacks will be executed regardless of what happens inside
+    /// the `mutation` function.
+    ///
+    /// ```rust
+    /// use squeak::BorrowedObservable;
+    ///
+    /// let mut name = String::from("DefaultName");
+    /// {
+    ///     let mut observable_name = BorrowedObservable::new(&mut name);
+    ///     observable_name.mutate(|n| n.push_str("X"));
+    /// }
+    /// assert_eq!(name, "DefaultNameX");
+    /// ```
+    pub fn mutate<M>(&mut self, mutation: M)
+    where
+        M: FnOnce(&mut T),
+    {
+        mutation(self.value);
+        self.delegate.broadcast_borrowed(self.value);
+    }
+}
+
+impl<T> Deref for BorrowedObservable<'_, T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        self.value
+    }
+}
@@ -0,0 +1,79 @@
+use alloc::boxed::Box;
+
+use crate::Observable;
+
+type Compute<'c, T, U> = Box<dyn Fn(&T) -> U + 'c>;
+
+/// Value derived from the value contained in an [`Observable`], created via
+/// [`Observable::cached_derive`].
+///
+/// The derived value is computed on first access, and only recomputed when accessed after
+/// the observable was mutated. Edits made via [`Observable::value_mut`] are not detected.
+///
+/// ```rust
+/// use squeak::Observable;
+///
+/// let mut side = Observable::new(2);
+/// let mut area = side.cached_derive(|s| s * s);
+/// assert_eq!(*area.get(&side), 4); // Computes the area
+/// assert_eq!(*area.get(&side), 4); // Uses the cached area
+///
+/// side.mutate(|s| *s = 3);
+/// assert_eq!(*area.get(&side), 9); // Computes the area again
+/// ```
+pub struct CachedValue<'c, T, U> {
+    compute: Compute<'c, T, U>,
+    // Version of the source observable the cached value was computed from
+    cached: Option<(u64, U)>,
+}
+
+impl<'c, T, U> CachedValue<'c, T, U> {
+    pub(crate) fn new<F: Fn(&T) -> U + 'c>(compute: F) -> Self {
+        Self {
+            compute: Box::new(compute),
+            cached: None,
+        }
+    }
+
+    /// Returns the derived value, computing it from the value contained in `source` if the
+    /// observable was mutated since the previous call.
+    ///
+    /// `source` must be the observable this cached value was created from.
+    ///
+    /// ```rust
+    /// use squeak::Observable;
+    ///
+    /// let name = Observable::new(String::from("Lisa"));
+    /// let mut length = name.cached_derive(|n| n.len());
+    /// assert_eq!(*length.get(&name), 4);
+    /// ```
+    pub fn get(&mut self, source: &Observable<'_, T>) -> &U {
+        if self.is_stale(source) {
+            self.cached = Some((source.version(), (self.compute)(source)));
+        }
+        &self.cached.as_ref().unwrap().1
+    }
+
+    /// Returns whether the derived value needs to be computed on the next call to
+    /// [`CachedValue::get`] with `source`.
+    ///
+    /// `source` must be the observable this cached value was created from.
+    ///
+    /// ```rust
+    /// use squeak::Observable;
+    ///
+    /// let mut name = Observable::new(String::from("Lisa"));
+    /// let mut length = name.cached_derive(|n| n.len());
+    /// assert!(length.is_stale(&name));
+    /// length.get(&name);
+    /// assert!(!length.is_stale(&name));
+    /// name.mutate(|n| n.push('!'));
+    /// assert!(length.is_stale(&name));
+    /// ```
+    pub fn is_stale(&self, source: &Observable<'_, T>) -> bool {
+        let version = source.version();
+        self.cached
+            .as_ref()
+            .map_or(true, |(cached_version, _)| *cached_version != version)
+    }
+}
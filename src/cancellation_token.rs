@@ -0,0 +1,69 @@
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+/// Flag which can be tripped to cancel a group of subscriptions at once, created via
+/// [`CancellationToken::new`].
+///
+/// Subscriptions registered with [`Delegate::subscribe_until`](crate::Delegate::subscribe_until)
+/// or [`Observable::subscribe_until`](crate::Observable::subscribe_until) are removed
+/// the next time their delegate or observable broadcasts after the token was cancelled.
+/// Clones of a token share the same flag, so a single token can cover subscriptions made on
+/// delegates and observables of different types.
+///
+/// ```rust
+/// use squeak::{CancellationToken, Delegate, Response};
+///
+/// let token = CancellationToken::new();
+/// let on_damage_received = Delegate::new();
+/// let on_renamed = Delegate::new();
+/// on_damage_received.subscribe_until(&token, |amount: &u32| {
+///     println!("Received {amount} damage");
+///     Response::StaySubscribed
+/// });
+/// on_renamed.subscribe_until(&token, |name: &String| {
+///     println!("Renamed to {name}");
+///     Response::StaySubscribed
+/// });
+///
+/// token.cancel();
+/// on_damage_received.broadcast(5); // Does not print anything
+/// on_renamed.broadcast(String::from("Lisa")); // Does not print anything
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels all subscriptions tied to this token. This cannot be undone.
+    ///
+    /// ```rust
+    /// use squeak::CancellationToken;
+    ///
+    /// let token = CancellationToken::new();
+    /// token.cancel();
+    /// assert!(token.is_cancelled());
+    /// ```
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    /// Returns whether [`CancellationToken::cancel`] was called on this token or any of its clones.
+    ///
+    /// ```rust
+    /// use squeak::CancellationToken;
+    ///
+    /// let token = CancellationToken::new();
+    /// let clone = token.clone();
+    /// assert!(!token.is_cancelled());
+    /// clone.cancel();
+    /// assert!(token.is_cancelled());
+    /// ```
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
@@ -0,0 +1,133 @@
+use alloc::collections::VecDeque;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use crate::Response;
+
+/// Fixed-size history of the values held by an [`Observable`](crate::Observable), created via
+/// [`Observable::chart_buffer`](crate::Observable::chart_buffer).
+///
+/// The buffer records a copy of the value after every mutation. Once it holds `capacity`
+/// values, the oldest one is dropped to make room for the newest one.
+///
+/// This type requires the `std` feature.
+///
+/// ```rust
+/// use squeak::Observable;
+///
+/// let mut frame_time = Observable::new(16);
+/// let history = frame_time.chart_buffer(3);
+/// for t in [17, 15, 33, 16] {
+///     frame_time.mutate(|f| *f = t);
+/// }
+/// assert_eq!(history.to_vec(), vec![15, 33, 16]);
+/// ```
+///
+/// The underlying subscription is cancelled by the first mutation following the destruction
+/// of the buffer.
+pub struct ChartBuffer<T> {
+    values: Arc<Mutex<VecDeque<T>>>,
+    capacity: usize,
+}
+
+impl<T: Clone> ChartBuffer<T> {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            values: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
+            capacity,
+        }
+    }
+
+    pub(crate) fn recorder(&self) -> impl FnMut(&T) -> Response {
+        let values = Arc::downgrade(&self.values);
+        let capacity = self.capacity;
+        move |value: &T| match values.upgrade() {
+            None => Response::CancelSubscription,
+            Some(values) => {
+                let mut values = values.lock().unwrap_or_else(PoisonError::into_inner);
+                if capacity > 0 {
+                    if values.len() == capacity {
+                        values.pop_front();
+                    }
+                    values.push_back(value.clone());
+                }
+                Response::StaySubscribed
+            }
+        }
+    }
+
+    /// Calls `f` with the recorded values, from oldest to newest, as a contiguous slice.
+    ///
+    /// ```rust
+    /// use squeak::Observable;
+    ///
+    /// let mut frame_time = Observable::new(16);
+    /// let history = frame_time.chart_buffer(10);
+    /// frame_time.mutate(|f| *f = 20);
+    /// frame_time.mutate(|f| *f = 30);
+    /// let peak = history.with_slice(|values| values.iter().copied().max());
+    /// assert_eq!(peak, Some(30));
+    /// ```
+    pub fn with_slice<R, F: FnOnce(&[T]) -> R>(&self, f: F) -> R {
+        f(self.lock().make_contiguous())
+    }
+
+    /// Returns a copy of the recorded values, from oldest to newest.
+    ///
+    /// ```rust
+    /// use squeak::Observable;
+    ///
+    /// let mut frame_time = Observable::new(16);
+    /// let history = frame_time.chart_buffer(10);
+    /// frame_time.mutate(|f| *f = 20);
+    /// assert_eq!(history.to_vec(), vec![20]);
+    /// ```
+    pub fn to_vec(&self) -> Vec<T> {
+        self.lock().iter().cloned().collect()
+    }
+
+    /// Returns how many values are currently recorded.
+    ///
+    /// ```rust
+    /// use squeak::Observable;
+    ///
+    /// let mut frame_time = Observable::new(16);
+    /// let history = frame_time.chart_buffer(10);
+    /// frame_time.mutate(|f| *f = 20);
+    /// assert_eq!(history.len(), 1);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Returns whether no values are recorded yet.
+    ///
+    /// ```rust
+    /// use squeak::Observable;
+    ///
+    /// let frame_time = Observable::new(16);
+    /// let history = frame_time.chart_buffer(10);
+    /// assert!(history.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Returns the maximum number of values this buffer holds.
+    ///
+    /// ```rust
+    /// use squeak::Observable;
+    ///
+    /// let frame_time = Observable::new(16);
+    /// let history = frame_time.chart_buffer(10);
+    /// assert_eq!(history.capacity(), 10);
+    /// ```
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    fn lock(&self) -> MutexGuard<'_, VecDeque<T>> {
+        self.values.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
@@ -0,0 +1,141 @@
+use alloc::fmt::Debug;
+use core::ops::Deref;
+
+use crate::{Delegate, Observable, Response, Subscription};
+
+/// Observable which carries an immutable context value, passed to callbacks registered via
+/// [`ContextualObservable::subscribe_with_context`].
+///
+/// This makes it possible to register the same callback on multiple observables and tell them apart.
+///
+/// ```rust
+/// use squeak::{Observable, Response};
+///
+/// let log_change = |name: &&str, value: &u32| {
+///     println!("{name} is now {value}");
+///     Response::StaySubscribed
+/// };
+///
+/// let mut health = Observable::with_context(100, "Health");
+/// let mut mana = Observable::with_context(50, "Mana");
+/// health.subscribe_with_context(log_change);
+/// mana.subscribe_with_context(log_change);
+///
+/// health.mutate(|h| *h -= 10); // Prints "Health is now 90"
+/// mana.mutate(|m| *m -= 5);    // Prints "Mana is now 45"
+/// ```
+#[derive(Debug)]
+pub struct ContextualObservable<'o, T, C> {
+    observable: Observable<'o, T>,
+    context: C,
+}
+
+impl<'o, T, C> ContextualObservable<'o, T, C> {
+    pub(crate) fn new(value: T, context: C) -> Self {
+        Self {
+            observable: Observable::new(value),
+            context,
+        }
+    }
+
+    /// Returns the context associated with this observable.
+    ///
+    /// ```rust
+    /// use squeak::Observable;
+    ///
+    /// let health = Observable::with_context(100, "Health");
+    /// assert_eq!(*health.context(), "Health");
+    /// ```
+    pub fn context(&self) -> &C {
+        &self.context
+    }
+
+    /// Registers a new callback that will be called when the value contained in this observable is mutated.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// let health = Observable::with_context(100, "Health");
+    /// health.subscribe(|updated_health| {
+    ///     println!("Health is now {updated_health}");
+    ///     Response::StaySubscribed
+    /// });
+    /// ```
+    pub fn subscribe<F: FnMut(&T) -> Response + 'o + Send>(&self, callback: F) -> Subscription {
+        self.observable.subscribe(callback)
+    }
+
+    /// Registers a new callback that will be called with the context and value of this observable
+    /// when the value is mutated.
+    ///
+    /// The callback receives its own copy of the context, made at subscription time.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// let mut health = Observable::with_context(100, "Health");
+    /// health.subscribe_with_context(|name, value| {
+    ///     println!("{name} is now {value}");
+    ///     Response::StaySubscribed
+    /// });
+    /// health.mutate(|h| *h -= 10); // Prints "Health is now 90"
+    /// ```
+    pub fn subscribe_with_context<F: FnMut(&C, &T) -> Response + 'o + Send>(
+        &self,
+        mut callback: F,
+    ) -> Subscription
+    where
+        C: Clone + Send + 'o,
+    {
+        let context = self.context.clone();
+        self.observable
+            .subscribe(move |value| callback(&context, value))
+    }
+
+    /// Removes a callback that was previously registered.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// let health = Observable::with_context(100, "Health");
+    /// let subscription = health.subscribe_with_context(|name, value| {
+    ///     println!("{name} is now {value}");
+    ///     Response::StaySubscribed
+    /// });
+    /// health.unsubscribe(subscription);
+    /// ```
+    pub fn unsubscribe(&self, subscription: Subscription) {
+        self.observable.unsubscribe(subscription);
+    }
+
+    /// Returns a reference to a delegate that will execute subscription functions
+    /// when the observable is mutated.
+    pub fn delegate(&self) -> &Delegate<'o, T> {
+        self.observable.delegate()
+    }
+
+    /// Execute a function which may mutate the value contained in this observable.
+    /// Subscription callbacks will be executed regardless of what happens inside
+    /// the `mutation` function.
+    ///
+    /// ```rust
+    /// use squeak::Observable;
+    ///
+    /// let mut name = Observable::with_context(String::from("DefaultName"), 0);
+    /// name.mutate(|n| n.push_str("X"));
+    /// assert_eq!(name.as_str(), "DefaultNameX");
+    /// ```
+    pub fn mutate<M>(&mut self, mutation: M)
+    where
+        M: FnOnce(&mut T),
+    {
+        self.observable.mutate(mutation);
+    }
+}
+
+impl<T, C> Deref for ContextualObservable<'_, T, C> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        &self.observable
+    }
+}
@@ -0,0 +1,116 @@
+use alloc::borrow::Borrow;
+use alloc::fmt::Debug;
+use core::cell::RefCell;
+
+use crate::{Delegate, Response, Subscription};
+
+/// Delegate which skips broadcasts of a value equal to the previously broadcast one.
+///
+/// ```rust
+/// use squeak::{DedupDelegate, Response};
+///
+/// let on_zone_changed = DedupDelegate::new();
+/// on_zone_changed.subscribe(|zone: &&str| {
+///     println!("Entered {zone}");
+///     Response::StaySubscribed
+/// });
+///
+/// on_zone_changed.broadcast("forest"); // Prints "Entered forest"
+/// on_zone_changed.broadcast("forest"); // Does not print anything
+/// on_zone_changed.broadcast("castle"); // Prints "Entered castle"
+/// ```
+#[derive(Debug, Default)]
+pub struct DedupDelegate<'d, T> {
+    delegate: Delegate<'d, T>,
+    last: RefCell<Option<T>>,
+}
+
+impl<'d, T> DedupDelegate<'d, T>
+where
+    T: Clone + PartialEq,
+{
+    pub fn new() -> Self {
+        Self {
+            delegate: Delegate::new(),
+            last: RefCell::new(None),
+        }
+    }
+
+    /// Registers a new callback that will be called when this delegate broadcasts a new value.
+    ///
+    /// ```rust
+    /// use squeak::{DedupDelegate, Response};
+    ///
+    /// let on_zone_changed = DedupDelegate::new();
+    /// on_zone_changed.subscribe(|zone: &&str| {
+    ///     println!("Entered {zone}");
+    ///     Response::StaySubscribed
+    /// });
+    /// ```
+    pub fn subscribe<C: FnMut(&T) -> Response + 'd + Send>(&self, callback: C) -> Subscription {
+        self.delegate.subscribe(callback)
+    }
+
+    /// Removes a callback that was previously registered.
+    ///
+    /// ```rust
+    /// use squeak::{DedupDelegate, Response};
+    ///
+    /// let on_zone_changed = DedupDelegate::new();
+    /// let subscription = on_zone_changed.subscribe(|zone: &&str| {
+    ///     println!("Entered {zone}");
+    ///     Response::StaySubscribed
+    /// });
+    /// on_zone_changed.unsubscribe(subscription);
+    /// ```
+    pub fn unsubscribe(&self, subscription: Subscription) {
+        self.delegate.unsubscribe(subscription);
+    }
+
+    /// Returns a reference to the underlying delegate. Broadcasting on it directly
+    /// bypasses deduplication.
+    pub fn delegate(&self) -> &Delegate<'d, T> {
+        &self.delegate
+    }
+
+    /// Executes all registered callbacks, providing `value` as their argument, unless `value`
+    /// is equal to the previous value broadcast by this delegate.
+    ///
+    /// ```rust
+    /// use squeak::{DedupDelegate, Response};
+    ///
+    /// let on_zone_changed = DedupDelegate::new();
+    /// on_zone_changed.subscribe(|zone: &String| {
+    ///     println!("Entered {zone}");
+    ///     Response::StaySubscribed
+    /// });
+    /// on_zone_changed.broadcast(String::from("forest")); // Prints "Entered forest"
+    /// on_zone_changed.broadcast(&String::from("forest")); // Does not print anything
+    /// ```
+    pub fn broadcast<U: Borrow<T>>(&self, value: U) {
+        let value = value.borrow();
+        if self.last.borrow().as_ref() == Some(value) {
+            return;
+        }
+        self.last.replace(Some(value.clone()));
+        self.delegate.broadcast_borrowed(value);
+    }
+
+    /// Forgets the previously broadcast value, so that the next broadcast always executes callbacks.
+    ///
+    /// ```rust
+    /// use squeak::{DedupDelegate, Response};
+    ///
+    /// let on_zone_changed = DedupDelegate::new();
+    /// on_zone_changed.subscribe(|zone: &&str| {
+    ///     println!("Entered {zone}");
+    ///     Response::StaySubscribed
+    /// });
+    /// on_zone_changed.broadcast("forest"); // Prints "Entered forest"
+    /// on_zone_changed.reset();
+    /// on_zone_changed.broadcast("forest"); // Prints "Entered forest"
+    /// ```
+    pub fn reset(&self) {
+        self.last.replace(None);
+    }
+}
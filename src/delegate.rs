@@ -1,43 +1,677 @@
+use alloc::fmt::{Debug, Display, Formatter};
+use alloc::rc::{Rc, Weak};
+use alloc::string::String;
 use alloc::vec::Vec;
-use alloc::{borrow::Borrow, boxed::Box, collections::BTreeMap, fmt::Debug};
+use alloc::{
+    borrow::{Borrow, Cow, ToOwned},
+    boxed::Box,
+    collections::BTreeMap,
+    sync::Arc,
+};
+use core::any::{Any, TypeId};
 
-use core::cell::RefCell;
-use core::sync::atomic::{AtomicU64, Ordering};
+use core::cell::{Cell, RefCell};
+use core::cmp::Reverse;
+use core::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+#[cfg(feature = "std")]
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::sync::mpsc::{self, Receiver, Sender};
+#[cfg(feature = "std")]
+use std::sync::{Mutex, PoisonError};
+#[cfg(feature = "std")]
+use std::time::Instant;
 
-type BoxedCallback<'a, T> = Box<dyn FnMut(&T) -> Response + 'a + Send>;
+use crate::CancellationToken;
+
+/// Type-erased callback, as stored by [`Delegate`].
+///
+/// Boxed callbacks can be used to populate a delegate via [`FromIterator`] or [`Extend`].
+pub type BoxedCallback<'a, T> = Box<dyn FnMut(&T) -> Response + 'a + Send>;
+type SequencedCallback<'a, T> = Box<dyn FnMut(u64, &T) -> Response + 'a + Send>;
+type ImmutableCallback<'a, T> = Box<dyn Fn(&T) -> Response + 'a + Send>;
+type HandlerCallback<'a, T> = Box<dyn FnMut(&T) -> Handled + 'a + Send>;
+type HandleCallback<'a, T> = Box<dyn FnMut(&T, &DelegateHandle) -> Response + 'a + Send>;
+type MutableCallback<'a, T> = Box<dyn FnMut(&mut T) -> Response + 'a + Send>;
+type ContextCallback<'a, T> = Box<dyn FnMut(&dyn Any, &T) -> Response + 'a + Send>;
+type FallibleCallback<'a, T> = Box<dyn FnMut(Option<&dyn Any>, &T) -> Response + 'a + Send>;
+
+/// Callback which can be shared between delegates, registered via [`Delegate::subscribe_arc`].
+pub type SharedCallback<'a, T> = Arc<dyn Fn(&T) -> Response + 'a + Send + Sync>;
 type SubscriptionId = u64;
+type InvocationHook<'a> = Box<dyn Fn(SubscriptionId) + 'a + Send>;
+type Teardown<'a> = Box<dyn FnOnce() + 'a + Send>;
 
 static NEXT_SUBSCRIPTION_ID: AtomicU64 = AtomicU64::new(0);
 
 /// Maintains a list of callbacks that can be explicitely triggered
 /// by calling [`Delegate::broadcast`].
+///
+/// The payload type may be unsized, which allows broadcasting borrowed views such as slices
+/// or string slices without copying them:
+///
+/// ```rust
+/// use squeak::{Delegate, Response};
+///
+/// let on_packet_received = Delegate::<[u8]>::new();
+/// on_packet_received.subscribe(|bytes| {
+///     println!("Received {} bytes", bytes.len());
+///     Response::StaySubscribed
+/// });
+///
+/// let buffer = vec![1, 2, 3, 4];
+/// on_packet_received.broadcast(&buffer[..2]); // Prints "Received 2 bytes"
+/// on_packet_received.broadcast(buffer); // Prints "Received 4 bytes"
+/// ```
+///
+/// When the payload type itself borrows data, like `Delegate<Event<'a>>`, the borrowed data must
+/// outlive the delegate. Data which only lives for the duration of a broadcast can still be sent
+/// by making the borrowed type the payload, as with `Delegate<[u8]>` above.
+///
+/// # Thread safety
+///
+/// Callbacks are required to be [`Send`], so a delegate can be moved to another thread along with
+/// its subscriptions, regardless of `T`. Delegates use interior mutability without locking, so they
+/// are never [`Sync`] and cannot be shared between threads:
+///
+/// ```compile_fail
+/// fn assert_sync<T: Sync>() {}
+/// assert_sync::<squeak::Delegate<u32>>();
+/// ```
+///
+/// [`Subscription`] values are both [`Send`] and [`Sync`].
 #[derive(Default)]
-pub struct Delegate<'d, T> {
-    pub(crate) subscriptions: RefCell<BTreeMap<SubscriptionId, BoxedCallback<'d, T>>>,
+pub struct Delegate<'d, T: ?Sized> {
+    subscriptions: RefCell<BTreeMap<SubscriptionId, Entry<'d, T>>>,
+    depth: Cell<u32>,
+    max_depth: Cell<Option<u32>>,
+    invocation_hook: RefCell<Option<InvocationHook<'d>>>,
+    deferred_unsubscriptions: RefCell<Vec<SubscriptionId>>,
+    broadcast_seq: Cell<u64>,
+    reentrant_broadcasts: Cell<u64>,
+    last_dispatched: Cell<Option<(i32, SubscriptionId)>>,
+    reentry_filter: RefCell<Option<Box<dyn ReentryFilter<T> + 'd + Send>>>,
+    recorder: RefCell<Option<Box<dyn Recorder<T> + 'd + Send>>>,
+}
+
+struct Entry<'d, T: ?Sized> {
+    // Temporarily empty while the callback is being executed
+    callback: Option<Callback<'d, T>>,
+    fallback: bool,
+    mutable: bool,
+    // Entries with higher priorities are executed first
+    priority: i32,
+    muted: bool,
+    group: Option<String>,
+    teardown: Option<Teardown<'d>>,
+    invocations: u64,
+}
+
+enum Callback<'d, T: ?Sized> {
+    Plain(BoxedCallback<'d, T>),
+    Sequenced(SequencedCallback<'d, T>),
+    // Receives the number of times the entry was invoked, including this time
+    Counted(SequencedCallback<'d, T>),
+    // Called through a shared reference, registered via Delegate::subscribe_shared
+    Immutable(ImmutableCallback<'d, T>),
+    Handler(HandlerCallback<'d, T>),
+    WithHandle(HandleCallback<'d, T>),
+    Mutable(MutableCallback<'d, T>),
+    Shared(SharedCallback<'d, T>),
+    Reducing(Box<dyn Reducer<T> + 'd + Send>),
+    // Only executed by broadcasts carrying a context of this type
+    Contextual(TypeId, ContextCallback<'d, T>),
+    // Receives the error sink of Delegate::broadcast_collecting, if any
+    Fallible(FallibleCallback<'d, T>),
+}
+
+enum Payload<'a, T: ?Sized> {
+    Shared(&'a T),
+    Exclusive(&'a mut T),
+    Contextual(&'a T, &'a dyn Any),
+    Collecting(&'a T, &'a dyn Any),
+}
+
+impl<T: ?Sized> Payload<'_, T> {
+    fn get(&self) -> &T {
+        match self {
+            Payload::Shared(value) => value,
+            Payload::Exclusive(value) => value,
+            Payload::Contextual(value, _) => value,
+            Payload::Collecting(value, _) => value,
+        }
+    }
+}
+
+impl<'d, T: ?Sized> Callback<'d, T> {
+    fn accepts(&self, payload: &Payload<T>) -> bool {
+        match (self, payload) {
+            (Callback::Contextual(context_type, _), Payload::Contextual(_, context)) => {
+                *context_type == (**context).type_id()
+            }
+            (Callback::Contextual(..), _) => false,
+            _ => true,
+        }
+    }
+
+    fn call(
+        &mut self,
+        payload: &mut Payload<T>,
+        seq: u64,
+        invocations: u64,
+        handle: &DelegateHandle,
+    ) -> (Response, Handled) {
+        if let (Callback::Mutable(callback), Payload::Exclusive(value)) =
+            (&mut *self, &mut *payload)
+        {
+            return (callback(value), Handled::Passed);
+        }
+        if let (Callback::Contextual(_, callback), Payload::Contextual(value, context)) =
+            (&mut *self, &*payload)
+        {
+            return (callback(*context, value), Handled::Passed);
+        }
+        if let Callback::Fallible(callback) = &mut *self {
+            let sink = match &*payload {
+                Payload::Collecting(_, sink) => Some(*sink),
+                _ => None,
+            };
+            return (callback(sink, payload.get()), Handled::Passed);
+        }
+        let value = payload.get();
+        match self {
+            Callback::Plain(callback) => (callback(value), Handled::Passed),
+            Callback::Sequenced(callback) => (callback(seq, value), Handled::Passed),
+            Callback::Counted(callback) => (callback(invocations, value), Handled::Passed),
+            Callback::Immutable(callback) => (callback(value), Handled::Passed),
+            Callback::Handler(callback) => (Response::StaySubscribed, callback(value)),
+            Callback::WithHandle(callback) => (callback(value, handle), Handled::Passed),
+            Callback::Shared(callback) => (callback(value), Handled::Passed),
+            Callback::Reducing(reducer) => {
+                reducer.reduce(value);
+                (Response::StaySubscribed, Handled::Passed)
+            }
+            Callback::Mutable(_) => {
+                unreachable!("mutable callbacks are only invoked by Delegate::broadcast_mut")
+            }
+            Callback::Contextual(..) => {
+                unreachable!(
+                    "contextual callbacks are only invoked by Delegate::broadcast_with_context"
+                )
+            }
+            Callback::Fallible(_) => unreachable!("fallible callbacks are invoked above"),
+        }
+    }
+}
+
+trait Reducer<T: ?Sized> {
+    fn reduce(&mut self, value: &T);
+    fn emit(&mut self);
+}
+
+struct Reducing<A, F, G> {
+    init: A,
+    // Empty until a value is broadcast after the previous flush
+    accumulator: Option<A>,
+    reduce: F,
+    emit: G,
+}
+
+impl<T: ?Sized, A: Clone, F: FnMut(A, &T) -> A, G: FnMut(A)> Reducer<T> for Reducing<A, F, G> {
+    fn reduce(&mut self, value: &T) {
+        let accumulator = self.accumulator.take().unwrap_or_else(|| self.init.clone());
+        self.accumulator = Some((self.reduce)(accumulator, value));
+    }
+
+    fn emit(&mut self) {
+        if let Some(accumulator) = self.accumulator.take() {
+            (self.emit)(accumulator);
+        }
+    }
+}
+
+impl<'d, T: ?Sized> Entry<'d, T> {
+    fn new(callback: Callback<'d, T>) -> Self {
+        Self {
+            callback: Some(callback),
+            fallback: false,
+            mutable: false,
+            priority: 0,
+            muted: false,
+            group: None,
+            teardown: None,
+            invocations: 0,
+        }
+    }
 }
 
 /// Represents a subscription created via [`Delegate::subscribe`] or [`Observable::subscribe`](crate::Observable::subscribe).
 ///
 /// It can be passed to [`Delegate::unsubscribe`] or [`Observable::unsubscribe`](crate::Observable::unsubscribe) to cancel the subscription.
-#[derive(Eq, Hash, PartialEq)]
+///
+/// Subscriptions are ordered by [`Subscription::id`], so subscriptions of a delegate sort in the
+/// order they were created.
+#[derive(Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct Subscription {
     id: SubscriptionId,
 }
 
+impl Subscription {
+    pub(crate) fn new() -> Self {
+        Self {
+            id: NEXT_SUBSCRIPTION_ID.fetch_add(1, Ordering::SeqCst),
+        }
+    }
+
+    /// Returns a number uniquely identifying this subscription.
+    ///
+    /// ```rust
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_respawn = Delegate::<()>::new();
+    /// let a = on_respawn.subscribe(|_| Response::StaySubscribed);
+    /// let b = on_respawn.subscribe(|_| Response::StaySubscribed);
+    /// assert_ne!(a.id(), b.id());
+    /// ```
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+}
+
+/// Subscription created via [`Delegate::subscribe_owning`], which cancels itself when dropped.
+///
+/// It only holds a weak reference to its delegate, so it can be stored freely and does not
+/// keep the delegate alive. Dropping it after the delegate was dropped has no effect.
+pub struct OwnedSubscription<'d, T: ?Sized> {
+    delegate: Weak<Delegate<'d, T>>,
+    subscription: Option<Subscription>,
+}
+
+impl<T: ?Sized> OwnedSubscription<'_, T> {
+    /// Returns a number uniquely identifying this subscription.
+    ///
+    /// ```rust
+    /// use std::rc::Rc;
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_respawn = Rc::new(Delegate::<()>::new());
+    /// let a = Delegate::subscribe_owning(&on_respawn, |_| Response::StaySubscribed);
+    /// let b = Delegate::subscribe_owning(&on_respawn, |_| Response::StaySubscribed);
+    /// assert_ne!(a.id(), b.id());
+    /// ```
+    pub fn id(&self) -> u64 {
+        self.subscription.as_ref().map_or(0, Subscription::id)
+    }
+}
+
+impl<T: ?Sized> Drop for OwnedSubscription<'_, T> {
+    fn drop(&mut self) {
+        if let (Some(delegate), Some(subscription)) =
+            (self.delegate.upgrade(), self.subscription.take())
+        {
+            delegate.unsubscribe(subscription);
+        }
+    }
+}
+
 /// Returned by [`Delegate`] and [`Observable`](crate::Observable) subscription callbacks.
 /// Depending on the value returned, the subscription will stay active or be cancelled.
+///
+/// More responses may be added in the future. Code matching on a `Response` outside of this crate
+/// must include a wildcard arm, or use [`Response::is_cancellation`]:
+///
+/// ```rust
+/// use squeak::Response;
+///
+/// fn describe(response: &Response) -> &'static str {
+///     match response {
+///         Response::StaySubscribed => "stay",
+///         Response::CancelSubscription => "cancel",
+///         _ => "other",
+///     }
+/// }
+/// ```
+#[non_exhaustive]
 pub enum Response {
     StaySubscribed,
     CancelSubscription,
 }
 
-impl<'d, T> Delegate<'d, T> {
+impl Response {
+    /// Returns [`Response::CancelSubscription`] if `condition` is true,
+    /// and [`Response::StaySubscribed`] otherwise.
+    ///
+    /// ```rust
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_damage_received = Delegate::new();
+    /// on_damage_received.subscribe(|amount| {
+    ///     println!("Received {amount} damage");
+    ///     Response::cancel_if(*amount > 10)
+    /// });
+    /// on_damage_received.broadcast(20); // Prints "Received 20 damage"
+    /// on_damage_received.broadcast(5); // Does not print anything
+    /// ```
+    pub fn cancel_if(condition: bool) -> Self {
+        match condition {
+            true => Response::CancelSubscription,
+            false => Response::StaySubscribed,
+        }
+    }
+
+    /// Returns whether this response cancels the subscription of the callback which returned it.
+    ///
+    /// ```rust
+    /// use squeak::Response;
+    ///
+    /// assert!(Response::CancelSubscription.is_cancellation());
+    /// assert!(!Response::StaySubscribed.is_cancellation());
+    /// ```
+    pub fn is_cancellation(&self) -> bool {
+        matches!(self, Response::CancelSubscription)
+    }
+}
+
+/// Passed to callbacks registered via [`Delegate::subscribe_with_handle`], allowing them to
+/// cancel other subscriptions of the delegate which is executing them.
+#[derive(Debug, Default)]
+pub struct DelegateHandle {
+    unsubscriptions: RefCell<Vec<SubscriptionId>>,
+}
+
+impl DelegateHandle {
+    /// Removes the subscription identified by `id` from the delegate once the outermost
+    /// broadcast completes. Subscriptions of other delegates are not affected.
+    pub fn unsubscribe(&self, id: u64) {
+        self.unsubscriptions.borrow_mut().push(id);
+    }
+}
+
+/// Returned by callbacks registered via [`Delegate::subscribe_handler`].
+/// Depending on the value returned, [`Delegate::dispatch`] will stop or keep executing callbacks.
+pub enum Handled {
+    Consumed,
+    Passed,
+}
+
+/// Passed to callbacks registered via [`Delegate::subscribe_controlled`], allowing them to
+/// manage their own subscription.
+#[derive(Debug, Default)]
+pub struct SubscriptionControl {
+    cancelled: bool,
+}
+
+impl SubscriptionControl {
+    /// Cancels the subscription once the callback returns.
+    pub fn cancel(&mut self) {
+        self.cancelled = true;
+    }
+}
+
+/// Error returned by [`Delegate::try_broadcast`] when a broadcast could not happen.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BroadcastError {
+    /// The broadcast was skipped because too many broadcasts were already in progress
+    /// on this delegate. See [`Delegate::set_max_depth`].
+    MaxDepthExceeded,
+    /// The broadcast was skipped because it was issued from within a broadcast of an equal value.
+    /// See [`Delegate::reject_recursive_same_value`].
+    RecursiveSameValue,
+}
+
+impl Display for BroadcastError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> alloc::fmt::Result {
+        match self {
+            BroadcastError::MaxDepthExceeded => f.write_str("maximum broadcast depth exceeded"),
+            BroadcastError::RecursiveSameValue => {
+                f.write_str("recursive broadcast of the value being broadcast")
+            }
+        }
+    }
+}
+
+/// Iterator returned by [`Delegate::broadcast_iter`], which executes one callback per step.
+///
+/// Each step yields the [`Subscription::id`] of the executed callback and its [`Response`].
+/// Subscriptions which respond with [`Response::CancelSubscription`] are removed before the
+/// step returns. Callbacks which are not reached before the iterator is dropped are not executed.
+///
+/// The delegate can be used freely between steps, including to subscribe, unsubscribe or start
+/// other broadcasts. Subscriptions created while the iterator is live are not executed by it, and
+/// subscriptions removed while it is live are skipped. The broadcast counts towards
+/// [`Delegate::broadcast_depth`] until the iterator is dropped.
+pub struct BroadcastIter<'a, 'd, T: ?Sized> {
+    delegate: &'a Delegate<'d, T>,
+    payload: Payload<'a, T>,
+    // Empty when the broadcast could not start
+    guard: Option<BroadcastGuard<'a, 'd, T>>,
+    pending: alloc::vec::IntoIter<SubscriptionId>,
+    fallbacks: Vec<SubscriptionId>,
+    num_invoked: usize,
+}
+
+impl<T: ?Sized> Iterator for BroadcastIter<'_, '_, T> {
+    type Item = (u64, Response);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let seq = self.guard.as_ref()?.seq;
+        loop {
+            let subscription = match self.pending.next() {
+                Some(subscription) => subscription,
+                None if self.num_invoked == 0 && !self.fallbacks.is_empty() => {
+                    self.pending = core::mem::take(&mut self.fallbacks).into_iter();
+                    continue;
+                }
+                None => return None,
+            };
+            let mut cancelled = Vec::new();
+            let invoked =
+                self.delegate
+                    .invoke(subscription, &mut self.payload, seq, &mut cancelled);
+            if invoked.is_none() {
+                continue;
+            }
+            self.num_invoked += 1;
+            if cancelled.is_empty() {
+                return Some((subscription, Response::StaySubscribed));
+            }
+            self.delegate.remove(subscription);
+            return Some((subscription, Response::CancelSubscription));
+        }
+    }
+}
+
+impl<T: ?Sized> Drop for BroadcastIter<'_, '_, T> {
+    fn drop(&mut self) {
+        if self.guard.is_some() {
+            self.delegate.apply_deferred_unsubscriptions();
+        }
+    }
+}
+
+/// Execution times measured by [`Delegate::broadcast_profiled`].
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, Default)]
+pub struct BroadcastProfile {
+    /// Time spent executing each callback, identified by its [`Subscription::id`], in execution order.
+    /// Callbacks which were not executed are omitted.
+    pub subscriptions: Vec<(u64, Duration)>,
+    /// Time spent executing the entire broadcast.
+    pub total: Duration,
+}
+
+/// Configuration of a subscription removed via [`Delegate::clear_returning_metadata`].
+///
+/// Callbacks cannot be recovered, but this information makes it possible to register
+/// equivalent callbacks with the same configuration.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SubscriptionMeta {
+    /// Id of the removed subscription, see [`Subscription::id`].
+    pub id: u64,
+    /// Callbacks with higher priorities were executed before callbacks with lower priorities.
+    pub priority: i32,
+    /// Group the subscription belonged to, see [`Delegate::subscribe_in_group`].
+    pub group: Option<String>,
+    /// Whether the subscription was registered via [`Delegate::subscribe_fallback`].
+    pub fallback: bool,
+    /// Whether the subscription was muted via [`Delegate::mute`].
+    pub muted: bool,
+}
+
+impl<T: ?Sized> Drop for Delegate<'_, T> {
+    fn drop(&mut self) {
+        let teardowns: Vec<_> = self
+            .subscriptions
+            .get_mut()
+            .values_mut()
+            .rev()
+            .filter_map(|entry| entry.teardown.take())
+            .collect();
+        for teardown in teardowns {
+            teardown();
+        }
+    }
+}
+
+// Broadcast context collecting the errors of callbacks registered via Delegate::subscribe_fallible
+struct ErrorSink<E>(RefCell<Vec<E>>);
+
+struct OnDrop<F: FnOnce()>(Option<F>);
+
+impl<F: FnOnce()> Drop for OnDrop<F> {
+    fn drop(&mut self) {
+        if let Some(f) = self.0.take() {
+            f();
+        }
+    }
+}
+
+struct DepthGuard<'a> {
+    depth: &'a Cell<u32>,
+}
+
+impl<'a> DepthGuard<'a> {
+    fn new(depth: &'a Cell<u32>) -> Self {
+        depth.set(depth.get() + 1);
+        Self { depth }
+    }
+}
+
+impl Drop for DepthGuard<'_> {
+    fn drop(&mut self) {
+        self.depth.set(self.depth.get() - 1);
+    }
+}
+
+// Decides whether a broadcast may start while others are in progress.
+trait ReentryFilter<T: ?Sized> {
+    fn enter(&mut self, value: &T) -> bool;
+    fn exit(&mut self);
+}
+
+struct SameValueFilter<T> {
+    in_flight: Vec<T>,
+}
+
+impl<T: Clone + PartialEq> ReentryFilter<T> for SameValueFilter<T> {
+    fn enter(&mut self, value: &T) -> bool {
+        if self.in_flight.contains(value) {
+            return false;
+        }
+        self.in_flight.push(value.clone());
+        true
+    }
+
+    fn exit(&mut self) {
+        self.in_flight.pop();
+    }
+}
+
+// Keeps track of the most recently broadcast value.
+trait Recorder<T: ?Sized> {
+    fn record(&mut self, value: &T);
+    fn last(&self) -> Option<&T>;
+}
+
+struct LastValue<T> {
+    value: Option<T>,
+}
+
+impl<T: Clone> Recorder<T> for LastValue<T> {
+    fn record(&mut self, value: &T) {
+        self.value = Some(value.clone());
+    }
+
+    fn last(&self) -> Option<&T> {
+        self.value.as_ref()
+    }
+}
+
+struct ReentryGuard<'a, 'd, T: ?Sized> {
+    filter: &'a RefCell<Option<Box<dyn ReentryFilter<T> + 'd + Send>>>,
+}
+
+// Held for the duration of a broadcast.
+struct BroadcastGuard<'a, 'd, T: ?Sized> {
+    seq: u64,
+    _reentry_guard: Option<ReentryGuard<'a, 'd, T>>,
+    _depth_guard: DepthGuard<'a>,
+}
+
+impl<T: ?Sized> Drop for ReentryGuard<'_, '_, T> {
+    fn drop(&mut self) {
+        if let Some(filter) = self.filter.borrow_mut().as_mut() {
+            filter.exit();
+        }
+    }
+}
+
+impl<'d, T: ?Sized> Delegate<'d, T> {
     pub fn new() -> Self {
         Self {
             subscriptions: RefCell::new(BTreeMap::new()),
+            depth: Cell::new(0),
+            max_depth: Cell::new(None),
+            invocation_hook: RefCell::new(None),
+            deferred_unsubscriptions: RefCell::new(Vec::new()),
+            broadcast_seq: Cell::new(0),
+            reentrant_broadcasts: Cell::new(0),
+            last_dispatched: Cell::new(None),
+            reentry_filter: RefCell::new(None),
+            recorder: RefCell::new(None),
         }
     }
 
+    /// Creates a delegate with one subscription per callback, registered in iteration order.
+    /// Unlike collecting callbacks via [`FromIterator`], this also returns the subscriptions, so
+    /// they can be cancelled individually.
+    ///
+    /// ```rust
+    /// use squeak::{BoxedCallback, Delegate, Response};
+    ///
+    /// let callbacks: Vec<BoxedCallback<u32>> = vec![
+    ///     Box::new(|amount| {
+    ///         println!("Logger A: received {amount} damage");
+    ///         Response::StaySubscribed
+    ///     }),
+    ///     Box::new(|amount| {
+    ///         println!("Logger B: received {amount} damage");
+    ///         Response::StaySubscribed
+    ///     }),
+    /// ];
+    /// let (on_damage_received, mut subscriptions) = Delegate::new_with_subscribers(callbacks);
+    /// on_damage_received.unsubscribe(subscriptions.remove(0));
+    /// on_damage_received.broadcast(5); // Prints "Logger B: received 5 damage"
+    /// ```
+    pub fn new_with_subscribers<I>(callbacks: I) -> (Self, Vec<Subscription>)
+    where
+        I: IntoIterator<Item = BoxedCallback<'d, T>>,
+    {
+        let delegate = Self::new();
+        let subscriptions = delegate.extend_callbacks(callbacks);
+        (delegate, subscriptions)
+    }
+
     /// Registers a new callback that will be called when this delegate broadcasts
     /// a new value.
     ///
@@ -56,97 +690,2333 @@ impl<'d, T> Delegate<'d, T> {
     /// again when [`broadcast`] is called in the future.
     ///
     pub fn subscribe<C: FnMut(&T) -> Response + 'd + Send>(&self, callback: C) -> Subscription {
-        let id = NEXT_SUBSCRIPTION_ID.fetch_add(1, Ordering::SeqCst);
-        let subscription = Subscription { id };
-        self.subscriptions
-            .borrow_mut()
-            .insert(subscription.id, Box::new(callback));
-        subscription
+        self.subscribe_boxed(Box::new(callback))
     }
 
-    /// Removes a callback that was previously registered.
+    /// Registers a new callback built by `builder`, which receives the subscription the callback
+    /// is registered under. This lets callbacks refer to their own subscription.
     ///
     /// ```rust
     /// use squeak::{Delegate, Response};
     ///
     /// let on_damage_received = Delegate::new();
-    /// let subscription = on_damage_received.subscribe(|amount| {
-    ///     println!("Received {amount} damage");
-    ///     Response::StaySubscribed
+    /// let subscription = on_damage_received.subscribe_with(|subscription| {
+    ///     let id = subscription.id();
+    ///     move |amount: &u32| {
+    ///         println!("Subscription #{id} received {amount} damage");
+    ///         Response::StaySubscribed
+    ///     }
     /// });
-    /// on_damage_received.broadcast(5); // Prints "Received 5 damage"
-    /// on_damage_received.unsubscribe(subscription);
-    /// on_damage_received.broadcast(10); // Does not print anything
+    /// on_damage_received.broadcast(5); // Prints "Subscription #<id> received 5 damage"
     /// ```
-    /// - Attempting to unsubscribe using a [`Subscription`] that was created by a different [`Delegate`] has no effect.
-    /// - Attempting to unsubscribe a [`Subscription`] multiple times has no effect.
-    /// - Attempting to unsubscribe from within callback function has no effect.
-    pub fn unsubscribe(&self, subscription: Subscription) {
-        self.subscriptions.borrow_mut().remove(&subscription.id);
+    pub fn subscribe_with<B, C>(&self, builder: B) -> Subscription
+    where
+        B: FnOnce(Subscription) -> C,
+        C: FnMut(&T) -> Response + 'd + Send,
+    {
+        let subscription = Subscription::new();
+        let callback = builder(Subscription {
+            id: subscription.id,
+        });
+        self.insert_as(
+            subscription,
+            Entry::new(Callback::Plain(Box::new(callback))),
+        )
     }
 
-    /// Executes all registered callbacks, providing `value` as their argument.
+    fn subscribe_boxed(&self, callback: BoxedCallback<'d, T>) -> Subscription {
+        self.insert(Entry::new(Callback::Plain(callback)))
+    }
+
+    fn insert(&self, entry: Entry<'d, T>) -> Subscription {
+        self.insert_as(Subscription::new(), entry)
+    }
+
+    fn insert_as(&self, subscription: Subscription, entry: Entry<'d, T>) -> Subscription {
+        self.subscriptions
+            .borrow_mut()
+            .insert(subscription.id, entry);
+        #[cfg(feature = "tracing")]
+        tracing::trace!(
+            delegate = core::any::type_name::<T>(),
+            subscription = subscription.id,
+            "subscribed"
+        );
+        subscription
+    }
+
+    // Deferred removals are only applied once the outermost broadcast completes, so that
+    // nested broadcasts do not cut the outer one short.
+    fn apply_deferred_unsubscriptions(&self) {
+        if self.depth.get() > 1 {
+            return;
+        }
+        for subscription in self.deferred_unsubscriptions.take() {
+            self.remove(subscription);
+        }
+    }
+
+    fn remove(&self, subscription: SubscriptionId) {
+        let removed = self.subscriptions.borrow_mut().remove(&subscription);
+        #[cfg(feature = "tracing")]
+        if removed.is_some() {
+            tracing::trace!(
+                delegate = core::any::type_name::<T>(),
+                subscription,
+                "unsubscribed"
+            );
+        }
+        drop(removed);
+    }
+
+    fn extend_callbacks<I: IntoIterator<Item = BoxedCallback<'d, T>>>(
+        &self,
+        callbacks: I,
+    ) -> Vec<Subscription> {
+        callbacks
+            .into_iter()
+            .map(|callback| self.subscribe_boxed(callback))
+            .collect()
+    }
+
+    /// Registers a new callback which does not need exclusive access to its captured state.
+    ///
+    /// Because `Fn` closures can be called through a shared reference, the same closure can be
+    /// registered on multiple delegates by passing a reference to it.
     ///
     /// ```rust
     /// use squeak::{Delegate, Response};
     ///
-    /// let on_renamed = Delegate::new();
-    /// on_renamed.subscribe(|new_name: &String| {
-    ///     println!("New name is {new_name}");
+    /// let log_damage = |amount: &u32| {
+    ///     println!("Received {amount} damage");
     ///     Response::StaySubscribed
-    /// });
-    /// on_renamed.broadcast(String::from("Lisa"));
-    /// on_renamed.broadcast(&String::from("Trevor"));
-    /// on_renamed.broadcast(&mut String::from("Jill"));
+    /// };
+    ///
+    /// let on_fire_damage = Delegate::new();
+    /// let on_ice_damage = Delegate::new();
+    /// on_fire_damage.subscribe_shared(&log_damage);
+    /// on_ice_damage.subscribe_shared(&log_damage);
+    /// on_fire_damage.broadcast(5); // Prints "Received 5 damage"
+    /// on_ice_damage.broadcast(3); // Prints "Received 3 damage"
     /// ```
-    pub fn broadcast<U: Borrow<T>>(&self, value: U) {
-        let subscriptions_to_notify = self
-            .subscriptions
-            .borrow()
-            .keys()
-            .copied()
-            .collect::<Vec<_>>();
-        for subscription in subscriptions_to_notify {
-            let (_, mut callback) = self
-                .subscriptions
-                .borrow_mut()
-                .remove_entry(&subscription)
-                .unwrap();
-            match callback(value.borrow()) {
-                Response::CancelSubscription => (),
-                Response::StaySubscribed => {
-                    self.subscriptions
-                        .borrow_mut()
-                        .insert(subscription, callback);
-                }
-            };
-        }
+    pub fn subscribe_shared<C: Fn(&T) -> Response + 'd + Send>(&self, callback: C) -> Subscription {
+        self.insert(Entry::new(Callback::Immutable(Box::new(callback))))
     }
-}
 
-impl Delegate<'_, ()> {
-    /// This convenience function broadcasts the unit type on delegates with no payload.
+    /// Registers a new asynchronous callback. Every time this delegate broadcasts a value, the
+    /// callback is called with a copy of the value and the future it returns is spawned on the
+    /// current [`tokio`] runtime.
+    ///
+    /// Spawned futures are not awaited: the broadcast does not wait for them to complete, and
+    /// futures spawned by successive broadcasts may complete in any order. Like [`tokio::spawn`],
+    /// broadcasting panics if called outside of a tokio runtime.
     ///
     /// ```rust
-    /// use squeak::{Delegate, Response};
+    /// use squeak::Delegate;
     ///
-    /// let on_respawn = Delegate::new();
-    /// on_respawn.subscribe(|_| {
-    ///    println!("Respawned");
-    ///    Response::StaySubscribed
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// let on_damage_received = Delegate::new();
+    /// on_damage_received.subscribe_async(|amount: u32| async move {
+    ///     println!("Received {amount} damage");
     /// });
-    /// on_respawn.notify();
+    /// on_damage_received.broadcast(5); // Eventually prints "Received 5 damage"
+    /// # }
     /// ```
-    pub fn notify(&self) {
-        self.broadcast(());
+    #[cfg(feature = "tokio")]
+    pub fn subscribe_async<F, Fut>(&self, mut callback: F) -> Subscription
+    where
+        T: Clone + Send + 'static,
+        F: FnMut(T) -> Fut + 'd + Send,
+        Fut: core::future::Future + Send + 'static,
+        Fut::Output: Send + 'static,
+    {
+        self.subscribe(move |value: &T| {
+            tokio::spawn(callback(value.clone()));
+            Response::StaySubscribed
+        })
+    }
+
+    /// Returns a [`Stream`](futures_core::Stream) which yields a copy of every value broadcast by
+    /// this delegate. The underlying subscription is cancelled by the first broadcast following
+    /// the destruction of the stream.
+    ///
+    /// This method requires the `futures` feature.
+    ///
+    /// ```rust
+    /// use futures::{executor, StreamExt};
+    /// use squeak::Delegate;
+    ///
+    /// let on_damage_received = Delegate::new();
+    /// let stream = on_damage_received.into_stream();
+    /// on_damage_received.broadcast(5);
+    /// on_damage_received.broadcast(30);
+    /// on_damage_received.broadcast(8);
+    /// let big_hits = executor::block_on(stream.filter(|a| futures::future::ready(*a > 10)).next());
+    /// assert_eq!(big_hits, Some(30));
+    /// ```
+    #[cfg(feature = "futures")]
+    pub fn into_stream(&self) -> impl futures_core::Stream<Item = T>
+    where
+        T: Clone + Send + 'd,
+    {
+        let (sender, receiver) = futures_channel::mpsc::unbounded();
+        self.subscribe(
+            move |value: &T| match sender.unbounded_send(value.clone()) {
+                Ok(()) => Response::StaySubscribed,
+                Err(_) => Response::CancelSubscription,
+            },
+        );
+        receiver
+    }
+
+    /// Registers a new callback which is not executed during broadcasts. Instead, every time this
+    /// delegate broadcasts a value, a task calling the callback with a copy of the value is handed
+    /// to `post`.
+    ///
+    /// This can be used to marshal callbacks to a specific thread or event loop, such as the main
+    /// thread of a UI framework.
+    ///
+    /// ```rust
+    /// use std::sync::mpsc;
+    /// use squeak::Delegate;
+    ///
+    /// let (sender, receiver) = mpsc::channel::<Box<dyn FnOnce() + Send>>();
+    /// let on_damage_received = Delegate::new();
+    /// on_damage_received.subscribe_on(
+    ///     move |task| sender.send(task).unwrap(),
+    ///     |amount: u32| println!("Received {amount} damage"),
+    /// );
+    ///
+    /// on_damage_received.broadcast(5); // Does not print anything
+    /// for task in receiver.try_iter() {
+    ///     task(); // Prints "Received 5 damage"
+    /// }
+    /// ```
+    pub fn subscribe_on<P, C>(&self, post: P, callback: C) -> Subscription
+    where
+        T: Clone + Send + 'static,
+        P: Fn(Box<dyn FnOnce() + Send>) + 'd + Send,
+        C: Fn(T) + Send + Sync + 'static,
+    {
+        let callback = Arc::new(callback);
+        self.subscribe(move |value: &T| {
+            let callback = callback.clone();
+            let value = value.clone();
+            post(Box::new(move || callback(value)));
+            Response::StaySubscribed
+        })
+    }
+
+    /// Registers a new callback that will be called when this delegate broadcasts a new value.
+    /// Instead of returning a [`Response`], the callback controls its subscription via
+    /// the [`SubscriptionControl`] it receives.
+    ///
+    /// ```rust
+    /// use squeak::Delegate;
+    ///
+    /// let on_damage_received = Delegate::new();
+    /// on_damage_received.subscribe_controlled(|amount, control| {
+    ///     println!("Received {amount} damage");
+    ///     if *amount > 10 {
+    ///         control.cancel();
+    ///     }
+    /// });
+    /// on_damage_received.broadcast(5); // Prints "Received 5 damage"
+    /// on_damage_received.broadcast(20); // Prints "Received 20 damage"
+    /// on_damage_received.broadcast(5); // Does not print anything
+    /// ```
+    pub fn subscribe_controlled<C: FnMut(&T, &mut SubscriptionControl) + 'd + Send>(
+        &self,
+        mut callback: C,
+    ) -> Subscription {
+        self.subscribe(move |value| {
+            let mut control = SubscriptionControl::default();
+            callback(value, &mut control);
+            match control.cancelled {
+                true => Response::CancelSubscription,
+                false => Response::StaySubscribed,
+            }
+        })
+    }
+
+    /// Registers a new callback, along with a function which will be called exactly once when
+    /// the subscription ends. This happens when the callback returns
+    /// [`Response::CancelSubscription`], when the subscription is removed via
+    /// [`Delegate::unsubscribe`], or when the delegate is dropped.
+    ///
+    /// To only react to the delegate being dropped, use [`Delegate::subscribe_with_teardown`]
+    /// instead.
+    ///
+    /// ```rust
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_damage_received = Delegate::new();
+    /// on_damage_received.subscribe_with_cleanup(
+    ///     |amount| {
+    ///         println!("Received {amount} damage");
+    ///         Response::CancelSubscription
+    ///     },
+    ///     || println!("No longer listening to damage"),
+    /// );
+    /// // Prints "Received 5 damage", then "No longer listening to damage"
+    /// on_damage_received.broadcast(5);
+    /// ```
+    pub fn subscribe_with_cleanup<C, E>(&self, mut callback: C, on_end: E) -> Subscription
+    where
+        C: FnMut(&T) -> Response + 'd + Send,
+        E: FnOnce() + 'd + Send,
+    {
+        let on_end = OnDrop(Some(on_end));
+        self.subscribe(move |value| {
+            let _on_end = &on_end;
+            callback(value)
+        })
+    }
+
+    /// Registers a new callback which is executed at most once per generation. The callback is
+    /// skipped by broadcasts happening while `generation` holds the same value as when the
+    /// callback was last executed.
+    ///
+    /// Sharing a generation counter, such as a frame number, between multiple delegates makes
+    /// it possible to coalesce notifications across all of them.
+    ///
+    /// ```rust
+    /// use std::sync::{atomic::{AtomicU64, Ordering}, Arc};
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let frame = Arc::new(AtomicU64::new(0));
+    /// let on_damage_received = Delegate::new();
+    /// on_damage_received.subscribe_on_generation(&frame, |amount| {
+    ///     println!("Received {amount} damage");
+    ///     Response::StaySubscribed
+    /// });
+    ///
+    /// on_damage_received.broadcast(5); // Prints "Received 5 damage"
+    /// on_damage_received.broadcast(3); // Does not print anything
+    /// frame.fetch_add(1, Ordering::SeqCst);
+    /// on_damage_received.broadcast(8); // Prints "Received 8 damage"
+    /// ```
+    pub fn subscribe_on_generation<C: FnMut(&T) -> Response + 'd + Send>(
+        &self,
+        generation: &Arc<AtomicU64>,
+        mut callback: C,
+    ) -> Subscription {
+        let generation = generation.clone();
+        let mut last_seen = None;
+        self.subscribe(move |value| {
+            let current = generation.load(Ordering::SeqCst);
+            if last_seen == Some(current) {
+                return Response::StaySubscribed;
+            }
+            last_seen = Some(current);
+            callback(value)
+        })
+    }
+
+    /// Registers a callback which observes every broadcast value and never cancels its subscription.
+    /// This is convenient to temporarily tap into a delegate, for example to log its activity.
+    ///
+    /// ```rust
+    /// use squeak::Delegate;
+    ///
+    /// let on_damage_received = Delegate::new();
+    /// let tap = on_damage_received.inspect(|amount| println!("Received {amount} damage"));
+    /// on_damage_received.broadcast(5); // Prints "Received 5 damage"
+    /// on_damage_received.unsubscribe(tap);
+    /// on_damage_received.broadcast(3); // Does not print anything
+    /// ```
+    pub fn inspect<F: Fn(&T) + 'd + Send>(&self, f: F) -> Subscription {
+        self.subscribe(move |value| {
+            f(value);
+            Response::StaySubscribed
+        })
+    }
+
+    /// Registers a callback which records copies of the next `n` broadcast values, then cancels
+    /// its subscription. Recorded values can be read from the returned vector at any time.
+    ///
+    /// This method requires the `std` feature.
+    ///
+    /// ```rust
+    /// use squeak::Delegate;
+    ///
+    /// let on_damage_received = Delegate::new();
+    /// let received = on_damage_received.collect_next(2);
+    /// on_damage_received.broadcast(5);
+    /// on_damage_received.broadcast(3);
+    /// on_damage_received.broadcast(8);
+    /// assert_eq!(*received.lock().unwrap(), vec![5, 3]);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn collect_next(&self, n: usize) -> Arc<Mutex<Vec<T>>>
+    where
+        T: Clone + Send + 'd,
+    {
+        let values = Arc::new(Mutex::new(Vec::with_capacity(n)));
+        if n == 0 {
+            return values;
+        }
+        let collected = values.clone();
+        self.subscribe(move |value: &T| {
+            let mut collected = collected.lock().unwrap_or_else(PoisonError::into_inner);
+            collected.push(value.clone());
+            match collected.len() >= n {
+                true => Response::CancelSubscription,
+                false => Response::StaySubscribed,
+            }
+        });
+        values
+    }
+
+    /// Registers a subscription which sends a copy of every broadcast value to the returned
+    /// [`Receiver`]. The subscription is cancelled by the first broadcast following the
+    /// destruction of the receiver. The channel is unbounded, so broadcasts never block.
+    ///
+    /// This method requires the `std` feature.
+    ///
+    /// ```rust
+    /// use std::thread;
+    /// use squeak::Delegate;
+    ///
+    /// let on_damage_received = Delegate::new();
+    /// let receiver = on_damage_received.subscribe_channel();
+    /// let worker = thread::spawn(move || {
+    ///     for amount in receiver {
+    ///         println!("Received {amount} damage");
+    ///     }
+    /// });
+    /// on_damage_received.broadcast(5); // Eventually prints "Received 5 damage"
+    /// drop(on_damage_received);
+    /// worker.join().unwrap();
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn subscribe_channel(&self) -> Receiver<T>
+    where
+        T: Clone + Send + 'd,
+    {
+        let (sender, receiver) = mpsc::channel();
+        self.subscribe(move |value: &T| Response::cancel_if(sender.send(value.clone()).is_err()));
+        receiver
+    }
+
+    /// Creates a delegate with a subscription which sends a copy of every broadcast value
+    /// through `sender`. The subscription is cancelled by the first broadcast following the
+    /// destruction of the corresponding receiver.
+    ///
+    /// This method requires the `std` feature.
+    ///
+    /// ```rust
+    /// use std::sync::mpsc;
+    /// use squeak::Delegate;
+    ///
+    /// let (sender, receiver) = mpsc::channel();
+    /// let on_damage_received = Delegate::from_sender(sender);
+    /// on_damage_received.broadcast(5);
+    /// assert_eq!(receiver.recv(), Ok(5));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn from_sender(sender: Sender<T>) -> Self
+    where
+        T: Clone + Send + 'd,
+    {
+        let delegate = Self::new();
+        delegate
+            .subscribe(move |value: &T| Response::cancel_if(sender.send(value.clone()).is_err()));
+        delegate
+    }
+
+    /// Registers a new callback which stops being called once `token` is cancelled.
+    /// The subscription is removed by the first broadcast following the cancellation.
+    ///
+    /// ```rust
+    /// use squeak::{CancellationToken, Delegate, Response};
+    ///
+    /// let token = CancellationToken::new();
+    /// let on_damage_received = Delegate::new();
+    /// on_damage_received.subscribe_until(&token, |amount| {
+    ///     println!("Received {amount} damage");
+    ///     Response::StaySubscribed
+    /// });
+    /// on_damage_received.broadcast(5); // Prints "Received 5 damage"
+    /// token.cancel();
+    /// on_damage_received.broadcast(3); // Does not print anything
+    /// ```
+    pub fn subscribe_until<C: FnMut(&T) -> Response + 'd + Send>(
+        &self,
+        token: &CancellationToken,
+        mut callback: C,
+    ) -> Subscription {
+        let token = token.clone();
+        self.subscribe(move |value| match token.is_cancelled() {
+            true => Response::CancelSubscription,
+            false => callback(value),
+        })
+    }
+
+    /// Registers a new callback which stops being called once `is_alive` returns `false`.
+    /// `is_alive` is checked before each invocation, and the subscription is removed by the first
+    /// broadcast where it returns `false`.
+    ///
+    /// ```rust
+    /// use std::sync::Arc;
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let window_open = Arc::new(AtomicBool::new(true));
+    /// let on_damage_received = Delegate::new();
+    /// let window = window_open.clone();
+    /// on_damage_received.subscribe_while_alive(
+    ///     move || window.load(Ordering::SeqCst),
+    ///     |amount| {
+    ///         println!("Received {amount} damage");
+    ///         Response::StaySubscribed
+    ///     },
+    /// );
+    /// on_damage_received.broadcast(5); // Prints "Received 5 damage"
+    /// window_open.store(false, Ordering::SeqCst);
+    /// on_damage_received.broadcast(3); // Does not print anything
+    /// ```
+    pub fn subscribe_while_alive<A, C>(&self, is_alive: A, mut callback: C) -> Subscription
+    where
+        A: Fn() -> bool + 'd + Send,
+        C: FnMut(&T) -> Response + 'd + Send,
+    {
+        self.subscribe(move |value| match is_alive() {
+            true => callback(value),
+            false => Response::CancelSubscription,
+        })
+    }
+
+    /// Registers a new callback which stops being called once `ttl` has elapsed since this call.
+    /// Expiry is checked by broadcasts: the subscription is removed by the first broadcast
+    /// which happens after it expired, not by a timer.
+    ///
+    /// This method requires the `std` feature.
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_damage_received = Delegate::new();
+    /// on_damage_received.subscribe_expiring(Duration::from_secs(5), |amount| {
+    ///     println!("Received {amount} damage");
+    ///     Response::StaySubscribed
+    /// });
+    /// on_damage_received.broadcast(5); // Prints "Received 5 damage"
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn subscribe_expiring<C: FnMut(&T) -> Response + 'd + Send>(
+        &self,
+        ttl: Duration,
+        mut callback: C,
+    ) -> Subscription {
+        let created = Instant::now();
+        self.subscribe(move |value| match created.elapsed() > ttl {
+            true => Response::CancelSubscription,
+            false => callback(value),
+        })
+    }
+
+    /// Registers a new callback which also receives a [`DelegateHandle`], through which it can
+    /// cancel other subscriptions of this delegate. Cancellations take effect once the outermost
+    /// broadcast completes.
+    ///
+    /// ```rust
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_progress = Delegate::new();
+    /// let spinner = on_progress.subscribe(|percent| {
+    ///     println!("Loading: {percent}%");
+    ///     Response::StaySubscribed
+    /// });
+    /// let spinner_id = spinner.id();
+    /// on_progress.subscribe_with_handle(move |percent, handle| {
+    ///     if *percent == 100 {
+    ///         handle.unsubscribe(spinner_id);
+    ///     }
+    ///     Response::StaySubscribed
+    /// });
+    /// on_progress.broadcast(50); // Prints "Loading: 50%"
+    /// on_progress.broadcast(100); // Prints "Loading: 100%"
+    /// on_progress.broadcast(100); // Does not print anything
+    /// ```
+    pub fn subscribe_with_handle<C: FnMut(&T, &DelegateHandle) -> Response + 'd + Send>(
+        &self,
+        callback: C,
+    ) -> Subscription {
+        self.insert(Entry::new(Callback::WithHandle(Box::new(callback))))
+    }
+
+    /// Registers a new callback which also receives the sequence number of each broadcast.
+    ///
+    /// Sequence numbers start at 1 and increase by one with every broadcast made by this
+    /// delegate, including nested broadcasts and broadcasts which did not reach this callback.
+    /// Gaps between the sequence numbers a callback receives indicate broadcasts it missed.
+    ///
+    /// ```rust
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_damage_received = Delegate::new();
+    /// on_damage_received.subscribe_seq(|seq, amount| {
+    ///     println!("Event #{seq}: received {amount} damage");
+    ///     Response::StaySubscribed
+    /// });
+    /// on_damage_received.broadcast(5); // Prints "Event #1: received 5 damage"
+    /// on_damage_received.broadcast(3); // Prints "Event #2: received 3 damage"
+    /// ```
+    pub fn subscribe_seq<C: FnMut(u64, &T) -> Response + 'd + Send>(
+        &self,
+        callback: C,
+    ) -> Subscription {
+        self.insert(Entry::new(Callback::Sequenced(Box::new(callback))))
+    }
+
+    /// Registers a new callback which also receives how many times it was called, including the
+    /// current call. See [`Delegate::invocation_count_of`].
+    ///
+    /// ```rust
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_damage_received = Delegate::new();
+    /// on_damage_received.subscribe_counted(|count, amount: &u32| {
+    ///     if count == 1 {
+    ///         println!("First blood!");
+    ///     }
+    ///     println!("Received {amount} damage");
+    ///     Response::StaySubscribed
+    /// });
+    /// on_damage_received.broadcast(5); // Prints "First blood!", then "Received 5 damage"
+    /// on_damage_received.broadcast(3); // Prints "Received 3 damage"
+    /// ```
+    pub fn subscribe_counted<C: FnMut(u64, &T) -> Response + 'd + Send>(
+        &self,
+        callback: C,
+    ) -> Subscription {
+        self.insert(Entry::new(Callback::Counted(Box::new(callback))))
+    }
+
+    /// Registers a new callback which may claim values sent via [`Delegate::dispatch`],
+    /// preventing subsequent callbacks from receiving them. Regular broadcasts execute
+    /// the callback but ignore its output.
+    ///
+    /// ```rust
+    /// use squeak::{Delegate, Handled};
+    ///
+    /// let on_key_pressed = Delegate::new();
+    /// on_key_pressed.subscribe_handler(|key| match key {
+    ///     'p' => {
+    ///         println!("Game paused");
+    ///         Handled::Consumed
+    ///     }
+    ///     _ => Handled::Passed,
+    /// });
+    /// on_key_pressed.subscribe_handler(|key| {
+    ///     println!("Typed {key}");
+    ///     Handled::Consumed
+    /// });
+    /// on_key_pressed.dispatch('p'); // Prints "Game paused"
+    /// on_key_pressed.dispatch('a'); // Prints "Typed a"
+    /// ```
+    pub fn subscribe_handler<C: FnMut(&T) -> Handled + 'd + Send>(
+        &self,
+        callback: C,
+    ) -> Subscription {
+        self.insert(Entry::new(Callback::Handler(Box::new(callback))))
+    }
+
+    /// Registers a new callback which can later be registered on other delegates via
+    /// [`Delegate::clone_subscriptions_into`].
+    ///
+    /// ```rust
+    /// use std::sync::Arc;
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_damage_received = Delegate::new();
+    /// on_damage_received.subscribe_arc(Arc::new(|amount: &u32| {
+    ///     println!("Received {amount} damage");
+    ///     Response::StaySubscribed
+    /// }));
+    /// on_damage_received.broadcast(5); // Prints "Received 5 damage"
+    /// ```
+    pub fn subscribe_arc(&self, callback: SharedCallback<'d, T>) -> Subscription {
+        self.insert(Entry::new(Callback::Shared(callback)))
+    }
+
+    /// Registers all callbacks which were registered on this delegate via
+    /// [`Delegate::subscribe_arc`] on `target` as well. Returns the new subscriptions
+    /// made on `target`.
+    ///
+    /// Callbacks which are being executed by a broadcast of this delegate are not copied.
+    ///
+    /// ```rust
+    /// use std::sync::Arc;
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let template = Delegate::new();
+    /// template.subscribe_arc(Arc::new(|amount: &u32| {
+    ///     println!("Received {amount} damage");
+    ///     Response::StaySubscribed
+    /// }));
+    ///
+    /// let on_damage_received = Delegate::new();
+    /// template.clone_subscriptions_into(&on_damage_received);
+    /// on_damage_received.broadcast(5); // Prints "Received 5 damage"
+    /// ```
+    pub fn clone_subscriptions_into(&self, target: &Delegate<'d, T>) -> Vec<Subscription> {
+        let callbacks: Vec<SharedCallback<'d, T>> = self
+            .subscriptions
+            .borrow()
+            .values()
+            .filter_map(|entry| match &entry.callback {
+                Some(Callback::Shared(callback)) => Some(callback.clone()),
+                _ => None,
+            })
+            .collect();
+        callbacks
+            .into_iter()
+            .map(|callback| target.subscribe_arc(callback))
+            .collect()
+    }
+
+    /// Registers a new callback which also receives the context passed to
+    /// [`Delegate::broadcast_with_context`]. The callback is only executed by broadcasts
+    /// carrying a context of type `Ctx`.
+    ///
+    /// ```rust
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_damage_received = Delegate::new();
+    /// on_damage_received.subscribe_with_broadcast_context(|source: &&str, amount: &u32| {
+    ///     println!("Received {amount} damage from {source}");
+    ///     Response::StaySubscribed
+    /// });
+    /// on_damage_received.broadcast_with_context(5, &"goblin"); // Prints "Received 5 damage from goblin"
+    /// on_damage_received.broadcast(5); // Does not print anything
+    /// ```
+    pub fn subscribe_with_broadcast_context<Ctx, C>(&self, mut callback: C) -> Subscription
+    where
+        Ctx: 'static,
+        C: FnMut(&Ctx, &T) -> Response + 'd + Send,
+    {
+        let callback: ContextCallback<'d, T> =
+            Box::new(move |context, value| match context.downcast_ref::<Ctx>() {
+                Some(context) => callback(context, value),
+                None => Response::StaySubscribed,
+            });
+        self.insert(Entry::new(Callback::Contextual(
+            TypeId::of::<Ctx>(),
+            callback,
+        )))
+    }
+
+    /// Registers a new callback which may modify values sent via [`Delegate::broadcast_mut`].
+    /// Such callbacks are not executed by other kinds of broadcasts.
+    ///
+    /// ```rust
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_damage_dealt = Delegate::new();
+    /// on_damage_dealt.subscribe_mut(|amount: &mut u32| {
+    ///     *amount *= 2;
+    ///     Response::StaySubscribed
+    /// });
+    /// let mut amount = 5;
+    /// on_damage_dealt.broadcast_mut(&mut amount);
+    /// assert_eq!(amount, 10);
+    /// ```
+    pub fn subscribe_mut<C: FnMut(&mut T) -> Response + 'd + Send>(
+        &self,
+        callback: C,
+    ) -> Subscription {
+        let mut entry = Entry::new(Callback::Mutable(Box::new(callback)));
+        entry.mutable = true;
+        self.insert(entry)
+    }
+
+    // Callbacks with higher priorities are executed first, callbacks with equal priorities are
+    // executed in the order they were registered.
+    pub(crate) fn subscribe_prioritized<C: FnMut(&T) -> Response + 'd + Send>(
+        &self,
+        priority: i32,
+        callback: C,
+    ) -> Subscription {
+        let mut entry = Entry::new(Callback::Plain(Box::new(callback)));
+        entry.priority = priority;
+        self.insert(entry)
+    }
+
+    /// Registers a new callback as part of a named group. Callbacks in a group are executed by
+    /// [`Delegate::broadcast`] like any other callback, and can also be executed separately from
+    /// the rest of the delegate via [`Delegate::broadcast_to_group`].
+    ///
+    /// ```rust
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_damage_received = Delegate::new();
+    /// on_damage_received.subscribe_in_group("critical", |amount: &u32| {
+    ///     println!("Received {amount} damage");
+    ///     Response::StaySubscribed
+    /// });
+    /// on_damage_received.broadcast_to_group("critical", 5); // Prints "Received 5 damage"
+    /// on_damage_received.broadcast_to_group("background", 5); // Does not print anything
+    /// ```
+    pub fn subscribe_in_group<C: FnMut(&T) -> Response + 'd + Send>(
+        &self,
+        group: &str,
+        callback: C,
+    ) -> Subscription {
+        let mut entry = Entry::new(Callback::Plain(Box::new(callback)));
+        entry.group = Some(String::from(group));
+        self.insert(entry)
+    }
+
+    /// Registers a new callback which is only executed while `gate` is set. Broadcasts made while
+    /// the gate is unset skip the callback, which stays subscribed. Sharing a gate between several
+    /// subscriptions makes it possible to enable or disable all of them at once.
+    ///
+    /// ```rust
+    /// use std::sync::Arc;
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let sound_enabled = Arc::new(AtomicBool::new(true));
+    /// let on_damage_received = Delegate::new();
+    /// on_damage_received.subscribe_gated(&sound_enabled, |amount: &u32| {
+    ///     println!("Playing hit sound for {amount} damage");
+    ///     Response::StaySubscribed
+    /// });
+    ///
+    /// on_damage_received.broadcast(5); // Prints "Playing hit sound for 5 damage"
+    /// sound_enabled.store(false, Ordering::SeqCst);
+    /// on_damage_received.broadcast(5); // Does not print anything
+    /// ```
+    pub fn subscribe_gated<C: FnMut(&T) -> Response + 'd + Send>(
+        &self,
+        gate: &Arc<AtomicBool>,
+        mut callback: C,
+    ) -> Subscription {
+        let gate = gate.clone();
+        self.subscribe(move |value: &T| match gate.load(Ordering::SeqCst) {
+            true => callback(value),
+            false => Response::StaySubscribed,
+        })
+    }
+
+    /// Registers a new callback, along with a `teardown` function which is called if this delegate
+    /// is dropped while the subscription is still active. When a delegate is dropped, teardown
+    /// functions are called in the reverse order of their registration.
+    ///
+    /// Unlike the function passed to [`Delegate::subscribe_with_cleanup`], `teardown` is not
+    /// called when the subscription is cancelled or removed via [`Delegate::unsubscribe`]. It is
+    /// simply dropped.
+    ///
+    /// ```rust
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_damage_received = Delegate::new();
+    /// on_damage_received.subscribe_with_teardown(
+    ///     |amount: &u32| {
+    ///         println!("Received {amount} damage");
+    ///         Response::StaySubscribed
+    ///     },
+    ///     || println!("No more damage events"),
+    /// );
+    /// drop(on_damage_received); // Prints "No more damage events"
+    /// ```
+    pub fn subscribe_with_teardown<C, D>(&self, callback: C, teardown: D) -> Subscription
+    where
+        C: FnMut(&T) -> Response + 'd + Send,
+        D: FnOnce() + 'd + Send,
+    {
+        let mut entry = Entry::new(Callback::Plain(Box::new(callback)));
+        entry.teardown = Some(Box::new(teardown));
+        self.insert(entry)
+    }
+
+    /// Registers a new callback which may fail. Fallible callbacks are executed by all broadcasts,
+    /// like regular callbacks. Their errors are returned by [`Delegate::broadcast_collecting`] for
+    /// errors of type `E`, and discarded by other broadcasts. Callbacks returning an error stay
+    /// subscribed.
+    ///
+    /// ```rust
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_save = Delegate::new();
+    /// on_save.subscribe_fallible(|path: &&str| match path.is_empty() {
+    ///     true => Err("empty path"),
+    ///     false => Ok(Response::StaySubscribed),
+    /// });
+    /// let errors: Vec<&str> = on_save.broadcast_collecting("");
+    /// assert_eq!(errors, vec!["empty path"]);
+    /// on_save.broadcast(""); // The error is discarded
+    /// ```
+    pub fn subscribe_fallible<E, C>(&self, mut callback: C) -> Subscription
+    where
+        E: 'static,
+        C: FnMut(&T) -> Result<Response, E> + 'd + Send,
+    {
+        let callback: FallibleCallback<'d, T> =
+            Box::new(move |sink, value| match callback(value) {
+                Ok(response) => response,
+                Err(error) => {
+                    if let Some(errors) = sink.and_then(|sink| sink.downcast_ref::<ErrorSink<E>>())
+                    {
+                        errors.0.borrow_mut().push(error);
+                    }
+                    Response::StaySubscribed
+                }
+            });
+        self.insert(Entry::new(Callback::Fallible(callback)))
+    }
+
+    /// Registers a fallback callback, which will only be called by broadcasts which did not
+    /// execute any regular callback.
+    ///
+    /// ```rust
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_command = Delegate::new();
+    /// on_command.subscribe_fallback(|command: &&str| {
+    ///     println!("Unknown command: {command}");
+    ///     Response::StaySubscribed
+    /// });
+    /// on_command.broadcast("jump"); // Prints "Unknown command: jump"
+    ///
+    /// on_command.subscribe(|command| {
+    ///     println!("Executing {command}");
+    ///     Response::StaySubscribed
+    /// });
+    /// on_command.broadcast("jump"); // Prints "Executing jump"
+    /// ```
+    pub fn subscribe_fallback<C: FnMut(&T) -> Response + 'd + Send>(
+        &self,
+        callback: C,
+    ) -> Subscription {
+        let mut entry = Entry::new(Callback::Plain(Box::new(callback)));
+        entry.fallback = true;
+        self.insert(entry)
+    }
+
+    /// Registers a subscription which folds every broadcast value into an accumulator, starting
+    /// from `init`. When [`Delegate::flush`] is called, `emit` receives the accumulated value and
+    /// the accumulator is reset to `init`. Flushes which follow no broadcast do not call `emit`.
+    ///
+    /// ```rust
+    /// use squeak::Delegate;
+    ///
+    /// let on_damage_received = Delegate::new();
+    /// on_damage_received.subscribe_reducing(
+    ///     0,
+    ///     |total, amount: &u32| total + amount,
+    ///     |total| println!("Received {total} damage this frame"),
+    /// );
+    /// on_damage_received.broadcast(5);
+    /// on_damage_received.broadcast(10);
+    /// on_damage_received.flush(); // Prints "Received 15 damage this frame"
+    /// ```
+    pub fn subscribe_reducing<A, F, G>(&self, init: A, reduce: F, emit: G) -> Subscription
+    where
+        A: Clone + 'd + Send,
+        F: FnMut(A, &T) -> A + 'd + Send,
+        G: FnMut(A) + 'd + Send,
+    {
+        self.insert(Entry::new(Callback::Reducing(Box::new(Reducing {
+            init,
+            accumulator: None,
+            reduce,
+            emit,
+        }))))
+    }
+
+    /// Registers a new callback that will be called when `delegate` broadcasts a new value.
+    /// The callback is unregistered when the returned [`OwnedSubscription`] is dropped.
+    ///
+    /// ```rust
+    /// use std::rc::Rc;
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_damage_received = Rc::new(Delegate::new());
+    /// let subscription = Delegate::subscribe_owning(&on_damage_received, |amount| {
+    ///     println!("Received {amount} damage");
+    ///     Response::StaySubscribed
+    /// });
+    /// on_damage_received.broadcast(5); // Prints "Received 5 damage"
+    /// drop(subscription);
+    /// on_damage_received.broadcast(10); // Does not print anything
+    /// ```
+    pub fn subscribe_owning<C: FnMut(&T) -> Response + 'd + Send>(
+        delegate: &Rc<Self>,
+        callback: C,
+    ) -> OwnedSubscription<'d, T> {
+        OwnedSubscription {
+            delegate: Rc::downgrade(delegate),
+            subscription: Some(delegate.subscribe(callback)),
+        }
+    }
+
+    /// Removes a callback that was previously registered.
+    ///
+    /// ```rust
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_damage_received = Delegate::new();
+    /// let subscription = on_damage_received.subscribe(|amount| {
+    ///     println!("Received {amount} damage");
+    ///     Response::StaySubscribed
+    /// });
+    /// on_damage_received.broadcast(5); // Prints "Received 5 damage"
+    /// on_damage_received.unsubscribe(subscription);
+    /// on_damage_received.broadcast(10); // Does not print anything
+    /// ```
+    /// - Attempting to unsubscribe using a [`Subscription`] that was created by a different [`Delegate`] has no effect.
+    /// - Attempting to unsubscribe a [`Subscription`] multiple times has no effect.
+    /// - Unsubscribing from within a callback takes effect immediately: callbacks removed this way
+    ///   are not executed by the rest of the current broadcast. A callback unsubscribing itself
+    ///   completes its current execution, and is then dropped.
+    pub fn unsubscribe(&self, subscription: Subscription) {
+        self.remove(subscription.id);
+    }
+
+    /// Removes a callback that was previously registered, like [`Delegate::unsubscribe`].
+    /// When called during a broadcast, the removal is postponed until the outermost broadcast
+    /// completes, so the callback still runs for the rest of it, nested broadcasts included.
+    ///
+    /// ```rust
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_damage_received = Delegate::new();
+    /// let subscription = on_damage_received.subscribe(|amount| {
+    ///     println!("Received {amount} damage");
+    ///     Response::StaySubscribed
+    /// });
+    /// on_damage_received.unsubscribe_deferred(subscription); // Not broadcasting, takes effect immediately
+    /// on_damage_received.broadcast(10); // Does not print anything
+    /// ```
+    pub fn unsubscribe_deferred(&self, subscription: Subscription) {
+        if self.depth.get() > 0 {
+            self.deferred_unsubscriptions
+                .borrow_mut()
+                .push(subscription.id);
+        } else {
+            self.unsubscribe(subscription);
+        }
+    }
+
+    /// Executes all registered callbacks, providing `value` as their argument.
+    ///
+    /// ```rust
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_renamed = Delegate::new();
+    /// on_renamed.subscribe(|new_name: &String| {
+    ///     println!("New name is {new_name}");
+    ///     Response::StaySubscribed
+    /// });
+    /// on_renamed.broadcast(String::from("Lisa"));
+    /// on_renamed.broadcast(&String::from("Trevor"));
+    /// on_renamed.broadcast(&mut String::from("Jill"));
+    /// ```
+    ///
+    /// Callbacks may subscribe, unsubscribe or broadcast on the delegate which is executing them:
+    /// - Callbacks registered during a broadcast are not executed by that broadcast.
+    /// - Callbacks removed during a broadcast, including by a nested broadcast, are not executed
+    ///   by the rest of that broadcast.
+    /// - A nested broadcast does not execute the callback(s) which triggered it.
+    ///
+    /// Callbacks are executed in the order they were registered. Use [`Delegate::broadcast_rev`]
+    /// to execute them in the reverse order.
+    pub fn broadcast<U: Borrow<T>>(&self, value: U) {
+        let _ = self.try_broadcast(value);
+    }
+
+    /// Executes all registered callbacks, providing `value` as their argument. Callbacks registered
+    /// via [`Delegate::subscribe_with_broadcast_context`] for contexts of type `Ctx` also receive
+    /// `context`. Other callbacks are executed as if by [`Delegate::broadcast`].
+    ///
+    /// ```rust
+    /// use squeak::{Delegate, Response};
+    ///
+    /// #[derive(Debug)]
+    /// enum Source {
+    ///     Player,
+    ///     Network,
+    /// }
+    ///
+    /// let on_renamed = Delegate::new();
+    /// on_renamed.subscribe_with_broadcast_context(|source: &Source, new_name: &String| {
+    ///     println!("Renamed to {new_name} by {source:?}");
+    ///     Response::StaySubscribed
+    /// });
+    /// on_renamed.subscribe(|new_name| {
+    ///     println!("Renamed to {new_name}");
+    ///     Response::StaySubscribed
+    /// });
+    /// // Prints "Renamed to Lisa by Player", then "Renamed to Lisa"
+    /// on_renamed.broadcast_with_context(String::from("Lisa"), &Source::Player);
+    /// ```
+    pub fn broadcast_with_context<Ctx: 'static, U: Borrow<T>>(&self, value: U, context: &Ctx) {
+        let _ = self
+            .broadcast_instrumented(Payload::Contextual(value.borrow(), context), |_, invoke| {
+                invoke()
+            });
+    }
+
+    /// Executes all registered callbacks, providing `value` as their argument, as if by
+    /// [`Delegate::broadcast`]. Every callback is executed, even after one of them failed. Returns
+    /// the errors of fallible callbacks registered via [`Delegate::subscribe_fallible`] for errors
+    /// of type `E`, in execution order. Errors of other types are discarded.
+    ///
+    /// Like [`Delegate::broadcast`], this does not execute callbacks registered via
+    /// [`Delegate::subscribe_with_broadcast_context`].
+    ///
+    /// ```rust
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_save = Delegate::new();
+    /// on_save.subscribe_fallible(|_: &u32| Err("disk full"));
+    /// on_save.subscribe_fallible(|_: &u32| Err("network down"));
+    /// on_save.subscribe(|slot| {
+    ///     println!("Saved slot {slot}");
+    ///     Response::StaySubscribed
+    /// });
+    /// let errors: Vec<&str> = on_save.broadcast_collecting(1); // Prints "Saved slot 1"
+    /// assert_eq!(errors, vec!["disk full", "network down"]);
+    /// ```
+    pub fn broadcast_collecting<E: 'static, U: Borrow<T>>(&self, value: U) -> Vec<E> {
+        let errors = ErrorSink(RefCell::new(Vec::<E>::new()));
+        let _ = self
+            .broadcast_instrumented(Payload::Collecting(value.borrow(), &errors), |_, invoke| {
+                invoke()
+            });
+        errors.0.into_inner()
+    }
+
+    /// Executes all registered callbacks, providing `value` as their argument. Unlike
+    /// [`Delegate::broadcast`], this method is not generic, which helps type inference.
+    ///
+    /// ```rust
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_renamed = Delegate::new();
+    /// on_renamed.subscribe(|new_name: &String| {
+    ///     println!("New name is {new_name}");
+    ///     Response::StaySubscribed
+    /// });
+    /// on_renamed.broadcast_borrowed(&String::from("Lisa")); // Prints "New name is Lisa"
+    /// ```
+    pub fn broadcast_borrowed(&self, value: &T) {
+        let _ = self.broadcast_internal(value);
+    }
+
+    /// Executes all registered callbacks, providing `value` as their argument. Borrowed and owned
+    /// values are both passed to callbacks by reference, so no copy is made in either case.
+    ///
+    /// ```rust
+    /// use std::borrow::Cow;
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_message = Delegate::<str>::new();
+    /// on_message.subscribe(|message| {
+    ///     println!("Received {message}");
+    ///     Response::StaySubscribed
+    /// });
+    /// on_message.broadcast_cow(Cow::Borrowed("hello")); // Prints "Received hello"
+    /// on_message.broadcast_cow(Cow::Owned(String::from("bye"))); // Prints "Received bye"
+    /// ```
+    pub fn broadcast_cow(&self, value: Cow<'_, T>)
+    where
+        T: ToOwned,
+    {
+        let _ = self.broadcast_internal(value.as_ref());
+    }
+
+    /// Executes all registered callbacks, providing `value` as their argument, starting with the
+    /// most recently registered callback.
+    ///
+    /// ```rust
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_key_pressed = Delegate::new();
+    /// on_key_pressed.subscribe(|key: &char| {
+    ///     println!("Game received {key}");
+    ///     Response::StaySubscribed
+    /// });
+    /// on_key_pressed.subscribe(|key: &char| {
+    ///     println!("Overlay received {key}");
+    ///     Response::StaySubscribed
+    /// });
+    /// // Prints "Overlay received a", then "Game received a"
+    /// on_key_pressed.broadcast_rev('a');
+    /// ```
+    pub fn broadcast_rev<U: Borrow<T>>(&self, value: U) {
+        let _ = self.broadcast_ordered(Payload::Shared(value.borrow()), true, |_, invoke| invoke());
+    }
+
+    /// Executes all registered callbacks, providing the value returned by `make_value` as their
+    /// argument. If this delegate has no subscriptions, `make_value` is not called.
+    ///
+    /// ```rust
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_frame = Delegate::<String>::new();
+    /// on_frame.broadcast_with(|| unreachable!()); // Does not build the value
+    ///
+    /// on_frame.subscribe(|report| {
+    ///     println!("{report}");
+    ///     Response::StaySubscribed
+    /// });
+    /// on_frame.broadcast_with(|| format!("Frame took {}ms", 16)); // Prints "Frame took 16ms"
+    /// ```
+    pub fn broadcast_with<F: FnOnce() -> T>(&self, make_value: F)
+    where
+        T: Sized,
+    {
+        if self.subscriber_count() > 0 {
+            self.broadcast(make_value());
+        }
+    }
+
+    /// Converts `value` into `T`, then executes all registered callbacks with the converted value
+    /// as their argument.
+    ///
+    /// ```rust
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_renamed = Delegate::new();
+    /// on_renamed.subscribe(|new_name: &String| {
+    ///     println!("New name is {new_name}");
+    ///     Response::StaySubscribed
+    /// });
+    /// on_renamed.broadcast_into("Lisa"); // Prints "New name is Lisa"
+    /// ```
+    pub fn broadcast_into<U: Into<T>>(&self, value: U)
+    where
+        T: Sized,
+    {
+        self.broadcast(value.into());
+    }
+
+    /// Executes all registered callbacks, providing `value` as their argument.
+    /// Unlike [`Delegate::broadcast`], this reports when the broadcast was skipped.
+    ///
+    /// ```rust
+    /// use squeak::{BroadcastError, Delegate};
+    ///
+    /// let on_renamed = Delegate::<String>::new();
+    /// on_renamed.set_max_depth(Some(0));
+    /// assert_eq!(
+    ///     on_renamed.try_broadcast(String::from("Lisa")),
+    ///     Err(BroadcastError::MaxDepthExceeded)
+    /// );
+    /// ```
+    pub fn try_broadcast<U: Borrow<T>>(&self, value: U) -> Result<(), BroadcastError> {
+        self.broadcast_internal(value.borrow()).map(|_| ())
+    }
+
+    /// Executes all callbacks registered via [`Delegate::subscribe_mut`], providing `value` as
+    /// their argument. Callbacks are executed in the order they were registered, and each of them
+    /// sees the modifications made by the previous ones.
+    ///
+    /// ```rust
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_message = Delegate::new();
+    /// on_message.subscribe_mut(|message: &mut String| {
+    ///     message.insert_str(0, "[12:00] ");
+    ///     Response::StaySubscribed
+    /// });
+    /// on_message.subscribe_mut(|message| {
+    ///     message.push_str(" (edited)");
+    ///     Response::StaySubscribed
+    /// });
+    /// let mut message = String::from("Hello");
+    /// on_message.broadcast_mut(&mut message);
+    /// assert_eq!(message, "[12:00] Hello (edited)");
+    /// ```
+    pub fn broadcast_mut(&self, value: &mut T) {
+        let _ = self.broadcast_instrumented(Payload::Exclusive(value), |_, invoke| invoke());
+    }
+
+    /// Executes registered callbacks in order, providing `value` as their argument, until `cancel`
+    /// is set. The flag is checked before each callback, so it can be set by callbacks as well as
+    /// by other threads. Callbacks which are skipped stay subscribed.
+    ///
+    /// ```rust
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    /// use squeak::{Delegate, Response};
+    ///
+    /// static CANCEL: AtomicBool = AtomicBool::new(false);
+    ///
+    /// let on_damage_received = Delegate::new();
+    /// on_damage_received.subscribe(|amount| {
+    ///     println!("Received {amount} damage");
+    ///     CANCEL.store(true, Ordering::SeqCst);
+    ///     Response::StaySubscribed
+    /// });
+    /// on_damage_received.subscribe(|amount| {
+    ///     println!("Also received {amount} damage");
+    ///     Response::StaySubscribed
+    /// });
+    /// on_damage_received.broadcast_cancellable(5, &CANCEL); // Prints "Received 5 damage"
+    /// ```
+    pub fn broadcast_cancellable<U: Borrow<T>>(&self, value: U, cancel: &AtomicBool) {
+        let _ =
+            self.broadcast_instrumented(Payload::Shared(value.borrow()), |_, invoke| match cancel
+                .load(Ordering::SeqCst)
+            {
+                true => None,
+                false => invoke(),
+            });
+    }
+
+    /// Executes registered callbacks in order, providing `value` as their argument, until one
+    /// of them returns [`Handled::Consumed`]. Returns whether the value was consumed.
+    ///
+    /// Only callbacks registered via [`Delegate::subscribe_handler`] can consume values,
+    /// other callbacks always let values pass through.
+    ///
+    /// ```rust
+    /// use squeak::{Delegate, Handled};
+    ///
+    /// let on_command = Delegate::new();
+    /// on_command.subscribe_handler(|command: &&str| match *command {
+    ///     "jump" => Handled::Consumed,
+    ///     _ => Handled::Passed,
+    /// });
+    /// assert!(on_command.dispatch("jump"));
+    /// assert!(!on_command.dispatch("fly"));
+    /// ```
+    pub fn dispatch<U: Borrow<T>>(&self, value: U) -> bool {
+        let mut consumed = false;
+        let _ = self.broadcast_instrumented(Payload::Shared(value.borrow()), |_, invoke| {
+            if consumed {
+                return None;
+            }
+            let handled = invoke();
+            consumed = matches!(handled, Some(Handled::Consumed));
+            handled
+        });
+        consumed
+    }
+
+    /// Executes the callbacks registered in `group` via [`Delegate::subscribe_in_group`], in order,
+    /// providing `value` as their argument. Other callbacks are not executed.
+    ///
+    /// ```rust
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_frame = Delegate::new();
+    /// on_frame.subscribe_in_group("physics", |dt: &f32| {
+    ///     println!("Simulating {dt}s");
+    ///     Response::StaySubscribed
+    /// });
+    /// on_frame.subscribe_in_group("render", |_: &f32| {
+    ///     println!("Drawing");
+    ///     Response::StaySubscribed
+    /// });
+    /// on_frame.broadcast_to_group("physics", 0.5); // Prints "Simulating 0.5s"
+    /// on_frame.broadcast(0.5); // Prints "Simulating 0.5s", then "Drawing"
+    /// ```
+    pub fn broadcast_to_group<U: Borrow<T>>(&self, group: &str, value: U) {
+        let _ =
+            self.broadcast_instrumented(Payload::Shared(value.borrow()), |subscription, invoke| {
+                let in_group = self
+                    .subscriptions
+                    .borrow()
+                    .get(&subscription)
+                    .map_or(false, |entry| entry.group.as_deref() == Some(group));
+                match in_group {
+                    true => invoke(),
+                    false => None,
+                }
+            });
+    }
+
+    /// Executes a single registered callback, providing `value` as its argument. Successive calls
+    /// execute callbacks in rotation, which makes it possible to spread work items across
+    /// subscribers. Returns whether a callback was executed.
+    ///
+    /// Fallback callbacks registered via [`Delegate::subscribe_fallback`] are never executed
+    /// by this method.
+    ///
+    /// ```rust
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_job = Delegate::new();
+    /// on_job.subscribe(|job: &u32| {
+    ///     println!("Worker A runs job {job}");
+    ///     Response::StaySubscribed
+    /// });
+    /// on_job.subscribe(|job: &u32| {
+    ///     println!("Worker B runs job {job}");
+    ///     Response::StaySubscribed
+    /// });
+    /// on_job.dispatch_round_robin(1); // Prints "Worker A runs job 1"
+    /// on_job.dispatch_round_robin(2); // Prints "Worker B runs job 2"
+    /// on_job.dispatch_round_robin(3); // Prints "Worker A runs job 3"
+    /// ```
+    pub fn dispatch_round_robin<U: Borrow<T>>(&self, value: U) -> bool {
+        let mut payload = Payload::Shared(value.borrow());
+        let guard = match self.begin_broadcast(&payload, false) {
+            Ok(guard) => guard,
+            Err(_) => return false,
+        };
+        let (subscriptions, _) = self.subscriptions_to_notify(false, false);
+        let mut cancelled = Vec::new();
+        let mut served = false;
+        // Subscriptions are listed by decreasing priority, then by id. Resuming after the last
+        // served position in that order still works if its subscription was removed since.
+        let order: Vec<(Reverse<i32>, SubscriptionId)> = {
+            let entries = self.subscriptions.borrow();
+            subscriptions
+                .iter()
+                .map(|id| {
+                    (
+                        Reverse(entries.get(id).map_or(0, |entry| entry.priority)),
+                        *id,
+                    )
+                })
+                .collect()
+        };
+        let start = match self.last_dispatched.get() {
+            None => 0,
+            Some((priority, last)) => order
+                .iter()
+                .position(|position| *position > (Reverse(priority), last))
+                .unwrap_or(0),
+        };
+        for offset in 0..subscriptions.len() {
+            let index = (start + offset) % subscriptions.len();
+            if self
+                .invoke(
+                    subscriptions[index],
+                    &mut payload,
+                    guard.seq,
+                    &mut cancelled,
+                )
+                .is_some()
+            {
+                self.last_dispatched
+                    .set(Some((order[index].0 .0, order[index].1)));
+                served = true;
+                break;
+            }
+        }
+        for subscription in &cancelled {
+            self.remove(*subscription);
+        }
+        self.apply_deferred_unsubscriptions();
+        served
+    }
+
+    /// Executes all registered callbacks, providing `value` as their argument.
+    /// Returns the subscriptions which were cancelled by their callback during this broadcast.
+    ///
+    /// ```rust
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_damage_received = Delegate::new();
+    /// let subscription = on_damage_received.subscribe(|amount| {
+    ///     println!("Received {amount} damage");
+    ///     Response::CancelSubscription
+    /// });
+    /// let cancelled = on_damage_received.broadcast_reporting(5); // Prints "Received 5 damage"
+    /// assert!(cancelled == vec![subscription]);
+    /// ```
+    pub fn broadcast_reporting<U: Borrow<T>>(&self, value: U) -> Vec<Subscription> {
+        self.broadcast_internal(value.borrow())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|id| Subscription { id })
+            .collect()
+    }
+
+    /// Executes all registered callbacks, providing `value` as their argument, and returns
+    /// the response of each callback alongside its subscription id. Unlike
+    /// [`Delegate::broadcast_reporting`], subscriptions are never removed by this method, even
+    /// if their callback asks for it. This is useful in tests, to check which subscriptions a
+    /// value would cancel without altering the delegate.
+    ///
+    /// The state of the delegate is left untouched: [`Delegate::current_seq`],
+    /// [`Delegate::invocation_count_of`] and [`Delegate::last_broadcast`] are not updated,
+    /// the hook set via [`Delegate::set_invocation_hook`] is not called, no tracing span is
+    /// entered, and unsubscriptions requested via [`DelegateHandle`] are ignored. Callbacks registered via
+    /// [`Delegate::subscribe_reducing`] are not executed, as that would alter their accumulated
+    /// value. Other callbacks are executed for real, so side effects inside them still happen.
+    ///
+    /// ```rust
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_damage_received = Delegate::new();
+    /// let subscription = on_damage_received.subscribe(|amount: &u32| match *amount > 10 {
+    ///     true => Response::CancelSubscription,
+    ///     false => Response::StaySubscribed,
+    /// });
+    /// let responses = on_damage_received.simulate(50);
+    /// assert_eq!(responses[0].0, subscription.id());
+    /// assert!(responses[0].1.is_cancellation());
+    /// assert!(on_damage_received.is_subscribed(&subscription));
+    /// ```
+    pub fn simulate<U: Borrow<T>>(&self, value: U) -> Vec<(u64, Response)> {
+        let mut payload = Payload::Shared(value.borrow());
+        let guard = match self.begin_broadcast(&payload, true) {
+            Ok(guard) => guard,
+            Err(_) => return Vec::new(),
+        };
+        let (subscriptions, fallbacks) = self.subscriptions_to_notify(false, false);
+        let mut simulate = |subscriptions: Vec<SubscriptionId>| -> Vec<(u64, Response)> {
+            subscriptions
+                .into_iter()
+                .filter_map(|subscription| {
+                    self.invoke_restoring(subscription, &mut payload, guard.seq, true)
+                        .map(|(response, _)| (subscription, response))
+                })
+                .collect()
+        };
+        let mut responses = simulate(subscriptions);
+        if responses.is_empty() {
+            responses = simulate(fallbacks);
+        }
+        responses
+    }
+
+    /// Returns an iterator which executes registered callbacks one at a time, providing `value`
+    /// as their argument. See [`BroadcastIter`] for details.
+    ///
+    /// No callback is executed if the broadcast would exceed the limit set via
+    /// [`Delegate::set_max_depth`].
+    ///
+    /// ```rust
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_damage_received = Delegate::new();
+    /// let subscription = on_damage_received.subscribe(|amount| {
+    ///     println!("Received {amount} damage");
+    ///     Response::CancelSubscription
+    /// });
+    /// on_damage_received.subscribe(|amount| {
+    ///     println!("Also received {amount} damage");
+    ///     Response::StaySubscribed
+    /// });
+    ///
+    /// let mut iter = on_damage_received.broadcast_iter(&5);
+    /// let (id, response) = iter.next().unwrap(); // Prints "Received 5 damage"
+    /// assert_eq!(id, subscription.id());
+    /// assert!(response.is_cancellation());
+    /// drop(iter); // The second callback is never executed
+    /// ```
+    pub fn broadcast_iter<'a>(&'a self, value: &'a T) -> BroadcastIter<'a, 'd, T> {
+        let payload = Payload::Shared(value);
+        let guard = self.begin_broadcast(&payload, false).ok();
+        let (pending, fallbacks) = match guard {
+            Some(_) => self.subscriptions_to_notify(false, false),
+            None => (Vec::new(), Vec::new()),
+        };
+        BroadcastIter {
+            delegate: self,
+            payload,
+            guard,
+            pending: pending.into_iter(),
+            fallbacks,
+            num_invoked: 0,
+        }
+    }
+
+    /// Executes all registered callbacks, providing `value` as their argument, and measures how long
+    /// each of them took to execute.
+    ///
+    /// This method requires the `std` feature.
+    ///
+    /// ```rust
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_damage_received = Delegate::new();
+    /// let subscription = on_damage_received.subscribe(|amount| {
+    ///     println!("Received {amount} damage");
+    ///     Response::StaySubscribed
+    /// });
+    /// let profile = on_damage_received.broadcast_profiled(5); // Prints "Received 5 damage"
+    /// assert_eq!(profile.subscriptions.len(), 1);
+    /// assert_eq!(profile.subscriptions[0].0, subscription.id());
+    /// assert!(profile.subscriptions[0].1 <= profile.total);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn broadcast_profiled<U: Borrow<T>>(&self, value: U) -> BroadcastProfile {
+        let mut subscriptions = Vec::new();
+        let start = Instant::now();
+        let payload = Payload::Shared(value.borrow());
+        let _ = self.broadcast_instrumented(payload, |subscription, invoke| {
+            let start = Instant::now();
+            let handled = invoke();
+            if handled.is_some() {
+                subscriptions.push((subscription, start.elapsed()));
+            }
+            handled
+        });
+        BroadcastProfile {
+            subscriptions,
+            total: start.elapsed(),
+        }
+    }
+
+    /// Calls the `emit` function of all subscriptions registered via
+    /// [`Delegate::subscribe_reducing`] which received values since the previous flush.
+    ///
+    /// ```rust
+    /// use squeak::Delegate;
+    ///
+    /// let on_coins_collected = Delegate::new();
+    /// on_coins_collected.subscribe_reducing(
+    ///     Vec::new(),
+    ///     |mut coins, coin: &&str| {
+    ///         coins.push(coin.to_string());
+    ///         coins
+    ///     },
+    ///     |coins| println!("Collected {}", coins.join(", ")),
+    /// );
+    /// on_coins_collected.broadcast("gold");
+    /// on_coins_collected.broadcast("silver");
+    /// on_coins_collected.flush(); // Prints "Collected gold, silver"
+    /// on_coins_collected.flush(); // Does not print anything
+    /// ```
+    pub fn flush(&self) {
+        let reducers: Vec<SubscriptionId> = self
+            .subscriptions
+            .borrow()
+            .iter()
+            .filter(|(_, entry)| matches!(entry.callback, Some(Callback::Reducing(_))))
+            .map(|(id, _)| *id)
+            .collect();
+        for subscription in reducers {
+            let callback = self
+                .subscriptions
+                .borrow_mut()
+                .get_mut(&subscription)
+                .and_then(|entry| entry.callback.take());
+            if let Some(Callback::Reducing(mut reducer)) = callback {
+                reducer.emit();
+                if let Some(entry) = self.subscriptions.borrow_mut().get_mut(&subscription) {
+                    entry.callback = Some(Callback::Reducing(reducer));
+                }
+            }
+        }
+    }
+
+    fn broadcast_internal(&self, value: &T) -> Result<Vec<SubscriptionId>, BroadcastError> {
+        self.broadcast_instrumented(Payload::Shared(value), |_, invoke| invoke())
+    }
+
+    fn broadcast_instrumented<I>(
+        &self,
+        payload: Payload<T>,
+        instrument: I,
+    ) -> Result<Vec<SubscriptionId>, BroadcastError>
+    where
+        I: FnMut(SubscriptionId, &mut dyn FnMut() -> Option<Handled>) -> Option<Handled>,
+    {
+        self.broadcast_ordered(payload, false, instrument)
+    }
+
+    // `instrument` is responsible for calling the `invoke` function it receives, which executes
+    // the callback of a subscription and returns `None` if it was not executed.
+    fn broadcast_ordered<I>(
+        &self,
+        mut payload: Payload<T>,
+        reverse: bool,
+        mut instrument: I,
+    ) -> Result<Vec<SubscriptionId>, BroadcastError>
+    where
+        I: FnMut(SubscriptionId, &mut dyn FnMut() -> Option<Handled>) -> Option<Handled>,
+    {
+        let guard = self.begin_broadcast(&payload, false)?;
+        let seq = guard.seq;
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!("broadcast", delegate = core::any::type_name::<T>(), seq)
+            .entered();
+        let (subscriptions_to_notify, fallbacks) =
+            self.subscriptions_to_notify(matches!(payload, Payload::Exclusive(_)), reverse);
+        let mut cancelled = Vec::new();
+        let mut num_invoked = 0;
+        for subscription in subscriptions_to_notify {
+            let handled = instrument(subscription, &mut || {
+                self.invoke(subscription, &mut payload, seq, &mut cancelled)
+            });
+            if handled.is_some() {
+                num_invoked += 1;
+            }
+        }
+        if num_invoked == 0 {
+            for subscription in fallbacks {
+                instrument(subscription, &mut || {
+                    self.invoke(subscription, &mut payload, seq, &mut cancelled)
+                });
+            }
+        }
+        if matches!(payload, Payload::Exclusive(_)) {
+            self.record(&payload);
+        }
+        for subscription in &cancelled {
+            self.remove(*subscription);
+        }
+        self.apply_deferred_unsubscriptions();
+        Ok(cancelled)
+    }
+
+    fn record(&self, payload: &Payload<T>) {
+        if let Some(recorder) = self.recorder.borrow_mut().as_mut() {
+            recorder.record(payload.get());
+        }
+    }
+
+    // Broadcasts made with `dry_run` do not update the sequence number, reentrancy statistics or
+    // recorded value of the delegate.
+    fn begin_broadcast(
+        &self,
+        payload: &Payload<T>,
+        dry_run: bool,
+    ) -> Result<BroadcastGuard<'_, 'd, T>, BroadcastError> {
+        if self.depth.get() > 0 && !dry_run {
+            self.reentrant_broadcasts
+                .set(self.reentrant_broadcasts.get() + 1);
+        }
+        if let Some(max_depth) = self.max_depth.get() {
+            if self.depth.get() >= max_depth {
+                return Err(BroadcastError::MaxDepthExceeded);
+            }
+        }
+        let reentry_guard = match payload {
+            Payload::Shared(value)
+            | Payload::Contextual(value, _)
+            | Payload::Collecting(value, _) => {
+                let entered = self
+                    .reentry_filter
+                    .borrow_mut()
+                    .as_mut()
+                    .map(|filter| filter.enter(value));
+                match entered {
+                    Some(false) => return Err(BroadcastError::RecursiveSameValue),
+                    Some(true) => Some(ReentryGuard {
+                        filter: &self.reentry_filter,
+                    }),
+                    None => None,
+                }
+            }
+            Payload::Exclusive(_) => None,
+        };
+        // Values sent via broadcast_mut are recorded once callbacks are done modifying them
+        if !dry_run && !matches!(payload, Payload::Exclusive(_)) {
+            self.record(payload);
+        }
+        let depth_guard = DepthGuard::new(&self.depth);
+        let seq = self.broadcast_seq.get() + 1;
+        if !dry_run {
+            self.broadcast_seq.set(seq);
+        }
+        Ok(BroadcastGuard {
+            seq,
+            _reentry_guard: reentry_guard,
+            _depth_guard: depth_guard,
+        })
+    }
+
+    // Returns the regular and fallback subscriptions a broadcast should execute, in order.
+    fn subscriptions_to_notify(
+        &self,
+        exclusive: bool,
+        reverse: bool,
+    ) -> (Vec<SubscriptionId>, Vec<SubscriptionId>) {
+        let subscriptions = self.subscriptions.borrow();
+        // Fast path for delegates which do not use priorities or fallbacks
+        if subscriptions
+            .values()
+            .all(|entry| entry.priority == 0 && !entry.fallback)
+        {
+            let ids = subscriptions
+                .iter()
+                .filter(|(_, entry)| entry.mutable == exclusive)
+                .map(|(id, _)| *id);
+            let regular = match reverse {
+                true => ids.rev().collect(),
+                false => ids.collect(),
+            };
+            return (regular, Vec::new());
+        }
+        let (mut fallbacks, mut regular): (Vec<_>, Vec<_>) = subscriptions
+            .iter()
+            .filter(|(_, entry)| entry.mutable == exclusive)
+            .map(|(id, entry)| (*id, entry.fallback, entry.priority))
+            .partition(|(_, fallback, _)| *fallback);
+        if reverse {
+            regular.reverse();
+            fallbacks.reverse();
+        }
+        regular.sort_by_key(|(_, _, priority)| Reverse(*priority));
+        fallbacks.sort_by_key(|(_, _, priority)| Reverse(*priority));
+        (
+            regular.into_iter().map(|(id, _, _)| id).collect(),
+            fallbacks.into_iter().map(|(id, _, _)| id).collect(),
+        )
+    }
+
+    fn invoke(
+        &self,
+        subscription: SubscriptionId,
+        payload: &mut Payload<T>,
+        seq: u64,
+        cancelled: &mut Vec<SubscriptionId>,
+    ) -> Option<Handled> {
+        let (response, handled) = self.invoke_restoring(subscription, payload, seq, false)?;
+        if response.is_cancellation() {
+            cancelled.push(subscription);
+        }
+        Some(handled)
+    }
+
+    // Invocations made with `dry_run` keep cancelled subscriptions, ignore unsubscriptions made
+    // via DelegateHandle, skip reducing callbacks and are not counted.
+    fn invoke_restoring(
+        &self,
+        subscription: SubscriptionId,
+        payload: &mut Payload<T>,
+        seq: u64,
+        dry_run: bool,
+    ) -> Option<(Response, Handled)> {
+        let (mut callback, invocations) = self
+            .subscriptions
+            .borrow_mut()
+            .get_mut(&subscription)
+            .filter(|entry| !entry.muted)
+            .filter(|entry| {
+                entry.callback.as_ref().map_or(false, |callback| {
+                    callback.accepts(payload)
+                        && !(dry_run && matches!(callback, Callback::Reducing(_)))
+                })
+            })
+            .and_then(|entry| {
+                let callback = entry.callback.take()?;
+                let invocations = entry.invocations + 1;
+                if !dry_run {
+                    entry.invocations = invocations;
+                }
+                Some((callback, invocations))
+            })?;
+        if !dry_run {
+            if let Some(hook) = self.invocation_hook.borrow().as_ref() {
+                hook(subscription);
+            }
+        }
+        let handle = DelegateHandle::default();
+        #[cfg(feature = "tracing")]
+        let _span = (!dry_run).then(|| {
+            tracing::trace_span!(
+                "invoke",
+                delegate = core::any::type_name::<T>(),
+                subscription
+            )
+            .entered()
+        });
+        let (response, handled) = callback.call(payload, seq, invocations, &handle);
+        if !dry_run {
+            let unsubscriptions = handle.unsubscriptions.into_inner();
+            if !unsubscriptions.is_empty() {
+                self.deferred_unsubscriptions
+                    .borrow_mut()
+                    .extend(unsubscriptions);
+            }
+        }
+        if dry_run || !response.is_cancellation() {
+            if let Some(entry) = self.subscriptions.borrow_mut().get_mut(&subscription) {
+                entry.callback = Some(callback);
+            }
+        }
+        Some((response, handled))
+    }
+
+    /// Returns how many subscriptions are active on this delegate.
+    ///
+    /// ```rust
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_damage_received = Delegate::<u32>::new();
+    /// on_damage_received.subscribe(|_| Response::StaySubscribed);
+    /// assert_eq!(on_damage_received.subscriber_count(), 1);
+    /// ```
+    pub fn subscriber_count(&self) -> usize {
+        self.subscriptions.borrow().len()
+    }
+
+    /// Returns a rough estimate of the memory used by this delegate, in bytes. The estimate
+    /// accounts for the bookkeeping of each subscription, but not for the data captured by
+    /// callbacks. It is meant to help spot delegates which grow unexpectedly.
+    ///
+    /// ```rust
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_damage_received = Delegate::<u32>::new();
+    /// let empty_size = on_damage_received.estimated_size();
+    /// on_damage_received.subscribe(|_| Response::StaySubscribed);
+    /// assert!(on_damage_received.estimated_size() > empty_size);
+    /// ```
+    pub fn estimated_size(&self) -> usize {
+        let entry_size = core::mem::size_of::<SubscriptionId>() + core::mem::size_of::<Entry<T>>();
+        core::mem::size_of::<Self>() + self.subscriber_count() * entry_size
+    }
+
+    /// Returns how many times the callback of a subscription was called. Returns zero if the
+    /// subscription is not active on this delegate.
+    ///
+    /// ```rust
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_damage_received = Delegate::<u32>::new();
+    /// let subscription = on_damage_received.subscribe(|_| Response::StaySubscribed);
+    /// on_damage_received.broadcast(5);
+    /// on_damage_received.broadcast(3);
+    /// assert_eq!(on_damage_received.invocation_count_of(&subscription), 2);
+    /// ```
+    pub fn invocation_count_of(&self, subscription: &Subscription) -> u64 {
+        self.subscriptions
+            .borrow()
+            .get(&subscription.id)
+            .map_or(0, |entry| entry.invocations)
+    }
+
+    /// Returns whether a subscription is active on this delegate.
+    ///
+    /// ```rust
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_damage_received = Delegate::<u32>::new();
+    /// let subscription = on_damage_received.subscribe(|_| Response::CancelSubscription);
+    /// assert!(on_damage_received.is_subscribed(&subscription));
+    /// on_damage_received.broadcast(5);
+    /// assert!(!on_damage_received.is_subscribed(&subscription));
+    /// ```
+    pub fn is_subscribed(&self, subscription: &Subscription) -> bool {
+        self.subscriptions.borrow().contains_key(&subscription.id)
+    }
+
+    /// Stops executing the callback of a subscription, without removing it from this delegate.
+    /// Muted callbacks keep their position in the execution order, and can be executed again
+    /// after calling [`Delegate::unmute`].
+    ///
+    /// ```rust
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_damage_received = Delegate::new();
+    /// let subscription = on_damage_received.subscribe(|amount| {
+    ///     println!("Received {amount} damage");
+    ///     Response::StaySubscribed
+    /// });
+    /// on_damage_received.mute(&subscription);
+    /// on_damage_received.broadcast(5); // Does not print anything
+    /// assert!(on_damage_received.is_subscribed(&subscription));
+    /// ```
+    pub fn mute(&self, subscription: &Subscription) {
+        self.set_muted(subscription, true);
+    }
+
+    /// Resumes executing the callback of a subscription muted via [`Delegate::mute`].
+    ///
+    /// ```rust
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_damage_received = Delegate::new();
+    /// let subscription = on_damage_received.subscribe(|amount| {
+    ///     println!("Received {amount} damage");
+    ///     Response::StaySubscribed
+    /// });
+    /// on_damage_received.mute(&subscription);
+    /// on_damage_received.unmute(&subscription);
+    /// on_damage_received.broadcast(5); // Prints "Received 5 damage"
+    /// ```
+    pub fn unmute(&self, subscription: &Subscription) {
+        self.set_muted(subscription, false);
+    }
+
+    fn set_muted(&self, subscription: &Subscription, muted: bool) {
+        if let Some(entry) = self.subscriptions.borrow_mut().get_mut(&subscription.id) {
+            entry.muted = muted;
+        }
+    }
+
+    /// Returns whether a subscription of this delegate is muted, see [`Delegate::mute`].
+    ///
+    /// ```rust
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_damage_received = Delegate::<u32>::new();
+    /// let subscription = on_damage_received.subscribe(|_| Response::StaySubscribed);
+    /// assert!(!on_damage_received.is_muted(&subscription));
+    /// on_damage_received.mute(&subscription);
+    /// assert!(on_damage_received.is_muted(&subscription));
+    /// ```
+    pub fn is_muted(&self, subscription: &Subscription) -> bool {
+        self.subscriptions
+            .borrow()
+            .get(&subscription.id)
+            .map_or(false, |entry| entry.muted)
+    }
+
+    /// Removes all subscriptions from this delegate, and returns the configuration of each of
+    /// them in the order they were registered. See [`SubscriptionMeta`].
+    ///
+    /// ```rust
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_damage_received = Delegate::<u32>::new();
+    /// on_damage_received.subscribe_in_group("ui", |_| Response::StaySubscribed);
+    /// let removed = on_damage_received.clear_returning_metadata();
+    /// assert_eq!(on_damage_received.subscriber_count(), 0);
+    /// assert_eq!(removed[0].group.as_deref(), Some("ui"));
+    /// ```
+    pub fn clear_returning_metadata(&self) -> Vec<SubscriptionMeta> {
+        let metadata: Vec<SubscriptionMeta> = self
+            .subscriptions
+            .borrow()
+            .iter()
+            .map(|(id, entry)| SubscriptionMeta {
+                id: *id,
+                priority: entry.priority,
+                group: entry.group.clone(),
+                fallback: entry.fallback,
+                muted: entry.muted,
+            })
+            .collect();
+        for meta in &metadata {
+            self.remove(meta.id);
+        }
+        metadata
+    }
+
+    /// Returns the ids of all subscriptions active on this delegate, in subscription order.
+    /// Broadcasts may execute callbacks in a different order, for example when some of them
+    /// were registered with a priority or as fallbacks.
+    ///
+    /// This method requires the `test-util` feature.
+    ///
+    /// ```rust
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_damage_received = Delegate::<u32>::new();
+    /// let a = on_damage_received.subscribe(|_| Response::StaySubscribed);
+    /// let b = on_damage_received.subscribe(|_| Response::StaySubscribed);
+    /// assert_eq!(on_damage_received.subscription_ids(), vec![a.id(), b.id()]);
+    /// ```
+    #[cfg(feature = "test-util")]
+    pub fn subscription_ids(&self) -> Vec<u64> {
+        self.subscriptions.borrow().keys().copied().collect()
+    }
+
+    /// Returns whether the subscription identified by `id` is active on this delegate.
+    ///
+    /// This method requires the `test-util` feature.
+    ///
+    /// ```rust
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_damage_received = Delegate::<u32>::new();
+    /// let subscription = on_damage_received.subscribe(|_| Response::StaySubscribed);
+    /// assert!(on_damage_received.contains(subscription.id()));
+    /// ```
+    #[cfg(feature = "test-util")]
+    pub fn contains(&self, id: u64) -> bool {
+        self.subscriptions.borrow().contains_key(&id)
+    }
+
+    /// Returns how many subscriptions active on this delegate satisfy a predicate.
+    ///
+    /// ```rust
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_damage_received = Delegate::<u32>::new();
+    /// let first = on_damage_received.subscribe(|_| Response::StaySubscribed);
+    /// on_damage_received.subscribe(|_| Response::StaySubscribed);
+    /// assert_eq!(on_damage_received.count_if(|s| s.id() > first.id()), 1);
+    /// ```
+    pub fn count_if<F: Fn(&Subscription) -> bool>(&self, predicate: F) -> usize {
+        self.subscriptions
+            .borrow()
+            .keys()
+            .filter(|id| predicate(&Subscription { id: **id }))
+            .count()
+    }
+
+    /// Registers a function that will be called with the id of each subscription,
+    /// right before its callback is executed by a broadcast. This replaces any
+    /// previously registered invocation hook.
+    ///
+    /// ```rust
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_damage_received = Delegate::new();
+    /// let subscription = on_damage_received.subscribe(|amount| {
+    ///     println!("Received {amount} damage");
+    ///     Response::StaySubscribed
+    /// });
+    /// on_damage_received.set_invocation_hook(|id| println!("Invoking subscription #{id}"));
+    ///
+    /// // Prints "Invoking subscription #<id>", then "Received 5 damage"
+    /// on_damage_received.broadcast(5);
+    /// ```
+    pub fn set_invocation_hook<H: Fn(u64) + 'd + Send>(&self, hook: H) {
+        self.invocation_hook.replace(Some(Box::new(hook)));
+    }
+
+    /// Removes the function registered via [`Delegate::set_invocation_hook`], if any.
+    ///
+    /// ```rust
+    /// use squeak::Delegate;
+    ///
+    /// let on_damage_received = Delegate::<u32>::new();
+    /// on_damage_received.set_invocation_hook(|id| println!("Invoking subscription #{id}"));
+    /// on_damage_received.clear_invocation_hook();
+    /// ```
+    pub fn clear_invocation_hook(&self) {
+        self.invocation_hook.replace(None);
+    }
+
+    /// Returns the sequence number of the most recent broadcast made by this delegate,
+    /// or zero if it never broadcasted. See [`Delegate::subscribe_seq`].
+    ///
+    /// ```rust
+    /// use squeak::Delegate;
+    ///
+    /// let on_damage_received = Delegate::<u32>::new();
+    /// assert_eq!(on_damage_received.current_seq(), 0);
+    /// on_damage_received.broadcast(5);
+    /// on_damage_received.broadcast(3);
+    /// assert_eq!(on_damage_received.current_seq(), 2);
+    /// ```
+    pub fn current_seq(&self) -> u64 {
+        self.broadcast_seq.get()
+    }
+
+    /// Returns how many broadcasts are currently in progress on this delegate.
+    /// This is greater than one when a callback broadcasts on the delegate that invoked it.
+    ///
+    /// ```rust
+    /// use squeak::Delegate;
+    ///
+    /// let on_damage_received = Delegate::<u32>::new();
+    /// assert_eq!(on_damage_received.broadcast_depth(), 0);
+    /// ```
+    pub fn broadcast_depth(&self) -> u32 {
+        self.depth.get()
+    }
+
+    /// Returns how many broadcasts were started on this delegate while another broadcast was
+    /// in progress. Nested broadcasts which were skipped, for example because of
+    /// [`Delegate::set_max_depth`], are counted too.
+    ///
+    /// ```rust
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_damage_received = Delegate::<u32>::new();
+    /// on_damage_received.broadcast(5);
+    /// assert_eq!(on_damage_received.reentrant_broadcast_count(), 0);
+    /// ```
+    pub fn reentrant_broadcast_count(&self) -> u64 {
+        self.reentrant_broadcasts.get()
+    }
+
+    /// Limits how many broadcasts can be in progress at the same time on this delegate.
+    /// Nested broadcasts which would exceed this limit are skipped, and reported as errors
+    /// by [`Delegate::try_broadcast`]. Passing `None` removes the limit, which is the default.
+    ///
+    /// ```rust
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_damage_received = Delegate::new();
+    /// on_damage_received.set_max_depth(Some(1)); // Disallow nested broadcasts
+    /// on_damage_received.subscribe(|amount| {
+    ///     println!("Received {amount} damage");
+    ///     Response::StaySubscribed
+    /// });
+    /// on_damage_received.broadcast(5); // Prints "Received 5 damage"
+    /// ```
+    pub fn set_max_depth(&self, max_depth: Option<u32>) {
+        self.max_depth.set(max_depth);
+    }
+
+    /// Skips broadcasts issued from within a callback when their value is equal to one
+    /// currently being broadcast. This breaks loops where a subscriber echoes the value it
+    /// received back to the delegate. Skipped broadcasts are reported as errors by
+    /// [`Delegate::try_broadcast`]. Values are cloned for the duration of each broadcast.
+    ///
+    /// ```rust
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_volume_changed = Delegate::new();
+    /// on_volume_changed.reject_recursive_same_value();
+    /// on_volume_changed.subscribe(|volume| {
+    ///     println!("Volume is now {volume}");
+    ///     Response::StaySubscribed
+    /// });
+    /// on_volume_changed.broadcast(11); // Prints "Volume is now 11"
+    /// ```
+    pub fn reject_recursive_same_value(&self)
+    where
+        T: Clone + PartialEq + Send + 'd,
+    {
+        *self.reentry_filter.borrow_mut() = Some(Box::new(SameValueFilter {
+            in_flight: Vec::new(),
+        }));
+    }
+}
+
+impl<'d, T: Clone + Send + 'd> Delegate<'d, T> {
+    /// Makes this delegate keep a copy of the value it broadcasts most recently, which can be
+    /// retrieved via [`Delegate::last_broadcast`]. Values broadcast before this call are not
+    /// remembered.
+    ///
+    /// ```rust
+    /// use squeak::Delegate;
+    ///
+    /// let on_zone_changed = Delegate::<String>::new();
+    /// on_zone_changed.remember_last_broadcast();
+    /// on_zone_changed.broadcast(String::from("forest"));
+    /// assert_eq!(on_zone_changed.last_broadcast().as_deref(), Some("forest"));
+    /// ```
+    pub fn remember_last_broadcast(&self) {
+        let mut recorder = self.recorder.borrow_mut();
+        if recorder.is_none() {
+            *recorder = Some(Box::new(LastValue { value: None }));
+        }
+    }
+
+    /// Returns a copy of the value most recently broadcast by this delegate, or `None` if
+    /// nothing was broadcast since calling [`Delegate::remember_last_broadcast`].
+    ///
+    /// For values sent via [`Delegate::broadcast_mut`], this is the value as left by callbacks.
+    ///
+    /// ```rust
+    /// use squeak::Delegate;
+    ///
+    /// let on_damage_received = Delegate::<u32>::new();
+    /// on_damage_received.remember_last_broadcast();
+    /// assert_eq!(on_damage_received.last_broadcast(), None);
+    /// on_damage_received.broadcast(5);
+    /// assert_eq!(on_damage_received.last_broadcast(), Some(5));
+    /// ```
+    pub fn last_broadcast(&self) -> Option<T> {
+        self.recorder
+            .borrow()
+            .as_ref()
+            .and_then(|recorder| recorder.last().cloned())
+    }
+}
+
+impl Delegate<'_, ()> {
+    /// This convenience function broadcasts the unit type on delegates with no payload.
+    ///
+    /// ```rust
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_respawn = Delegate::new();
+    /// on_respawn.subscribe(|_| {
+    ///    println!("Respawned");
+    ///    Response::StaySubscribed
+    /// });
+    /// on_respawn.notify();
+    /// ```
+    pub fn notify(&self) {
+        self.broadcast(());
+    }
+}
+
+impl<'d, A, B> Delegate<'d, (A, B)> {
+    /// Registers a new callback which receives the elements of each broadcast pair
+    /// as separate arguments.
+    ///
+    /// ```rust
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_damage_received = Delegate::new();
+    /// on_damage_received.subscribe2(|amount: &u32, source: &&str| {
+    ///     println!("Received {amount} damage from {source}");
+    ///     Response::StaySubscribed
+    /// });
+    /// on_damage_received.broadcast((5, "goblin")); // Prints "Received 5 damage from goblin"
+    /// ```
+    pub fn subscribe2<C: FnMut(&A, &B) -> Response + 'd + Send>(
+        &self,
+        mut callback: C,
+    ) -> Subscription {
+        self.subscribe(move |(a, b)| callback(a, b))
+    }
+}
+
+impl<'d, A, B, C> Delegate<'d, (A, B, C)> {
+    /// Registers a new callback which receives the elements of each broadcast triple
+    /// as separate arguments.
+    ///
+    /// ```rust
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_moved = Delegate::new();
+    /// on_moved.subscribe3(|x: &f32, y: &f32, z: &f32| {
+    ///     println!("Moved to ({x}, {y}, {z})");
+    ///     Response::StaySubscribed
+    /// });
+    /// on_moved.broadcast((1.0, 2.0, 3.0)); // Prints "Moved to (1, 2, 3)"
+    /// ```
+    pub fn subscribe3<F: FnMut(&A, &B, &C) -> Response + 'd + Send>(
+        &self,
+        mut callback: F,
+    ) -> Subscription {
+        self.subscribe(move |(a, b, c)| callback(a, b, c))
+    }
+}
+
+impl<'d, T: ?Sized> FromIterator<BoxedCallback<'d, T>> for Delegate<'d, T> {
+    /// Creates a delegate with one subscription per callback, registered in iteration order.
+    ///
+    /// ```rust
+    /// use squeak::{BoxedCallback, Delegate, Response};
+    ///
+    /// let callbacks: Vec<BoxedCallback<u32>> = vec![
+    ///     Box::new(|amount| {
+    ///         println!("Received {amount} damage");
+    ///         Response::StaySubscribed
+    ///     }),
+    ///     Box::new(|_| Response::CancelSubscription),
+    /// ];
+    /// let on_damage_received: Delegate<u32> = callbacks.into_iter().collect();
+    /// on_damage_received.broadcast(5); // Prints "Received 5 damage"
+    /// ```
+    fn from_iter<I: IntoIterator<Item = BoxedCallback<'d, T>>>(iter: I) -> Self {
+        let delegate = Self::new();
+        delegate.extend_callbacks(iter);
+        delegate
+    }
+}
+
+impl<'d, T: ?Sized> Extend<BoxedCallback<'d, T>> for Delegate<'d, T> {
+    /// Registers one subscription per callback, in iteration order.
+    ///
+    /// ```rust
+    /// use squeak::{BoxedCallback, Delegate, Response};
+    ///
+    /// let mut on_respawn = Delegate::new();
+    /// let callbacks: Vec<BoxedCallback<()>> = vec![
+    ///     Box::new(|_| Response::StaySubscribed),
+    ///     Box::new(|_| Response::StaySubscribed),
+    /// ];
+    /// on_respawn.extend(callbacks);
+    /// on_respawn.notify();
+    /// ```
+    fn extend<I: IntoIterator<Item = BoxedCallback<'d, T>>>(&mut self, iter: I) {
+        self.extend_callbacks(iter);
     }
 }
 
 impl<T> Debug for Delegate<'_, T>
 where
-    T: Debug,
+    T: Debug + ?Sized,
 {
-    fn fmt(&self, f: &mut alloc::fmt::Formatter<'_>) -> alloc::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> alloc::fmt::Result {
         f.debug_struct("Delegate")
             .field(
                 "subscriptions",
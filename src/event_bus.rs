@@ -0,0 +1,140 @@
+use alloc::fmt::{Debug, Formatter};
+use alloc::{borrow::Borrow, collections::BTreeMap, rc::Rc};
+use core::any::{Any, TypeId};
+use core::cell::RefCell;
+
+use crate::{Delegate, Response, Subscription};
+
+/// Collection of delegates, one per event type.
+///
+/// Events can be any `'static` type, and are dispatched to the callbacks which subscribed
+/// to that exact type.
+///
+/// ```rust
+/// use squeak::{EventBus, Response};
+///
+/// struct DamageReceived(u32);
+/// struct Respawned;
+///
+/// let events = EventBus::new();
+/// events.subscribe(|event: &DamageReceived| {
+///     println!("Received {} damage", event.0);
+///     Response::StaySubscribed
+/// });
+/// events.subscribe(|_: &Respawned| {
+///     println!("Respawned");
+///     Response::StaySubscribed
+/// });
+///
+/// events.emit(DamageReceived(5)); // Prints "Received 5 damage"
+/// events.emit(Respawned);         // Prints "Respawned"
+/// ```
+///
+/// Delegates are stored type-erased and looked up by the [`TypeId`] of the event type.
+/// Because the delegate for `E` is always stored under `TypeId::of::<E>()`, downcasting it
+/// back to `Delegate<E>` cannot fail.
+#[derive(Default)]
+pub struct EventBus {
+    delegates: RefCell<BTreeMap<TypeId, Rc<dyn Any>>>,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        Self {
+            delegates: RefCell::new(BTreeMap::new()),
+        }
+    }
+
+    /// Returns the delegate used to broadcast events of type `E`, creating it if needed.
+    ///
+    /// ```rust
+    /// use squeak::{EventBus, Response};
+    ///
+    /// struct Respawned;
+    ///
+    /// let events = EventBus::new();
+    /// let on_respawn = events.delegate::<Respawned>();
+    /// on_respawn.subscribe(|_| {
+    ///     println!("Respawned");
+    ///     Response::StaySubscribed
+    /// });
+    /// events.emit(Respawned); // Prints "Respawned"
+    /// ```
+    pub fn delegate<E: 'static>(&self) -> Rc<Delegate<'static, E>> {
+        let delegate = self
+            .delegates
+            .borrow_mut()
+            .entry(TypeId::of::<E>())
+            .or_insert_with(|| Rc::new(Delegate::<'static, E>::new()))
+            .clone();
+        delegate.downcast().unwrap()
+    }
+
+    /// Registers a new callback that will be called when an event of type `E` is emitted.
+    ///
+    /// ```rust
+    /// use squeak::{EventBus, Response};
+    ///
+    /// struct DamageReceived(u32);
+    ///
+    /// let events = EventBus::new();
+    /// events.subscribe(|event: &DamageReceived| {
+    ///     println!("Received {} damage", event.0);
+    ///     Response::StaySubscribed
+    /// });
+    /// ```
+    pub fn subscribe<E: 'static, C: FnMut(&E) -> Response + 'static + Send>(
+        &self,
+        callback: C,
+    ) -> Subscription {
+        self.delegate::<E>().subscribe(callback)
+    }
+
+    /// Removes a callback that was previously registered for events of type `E`.
+    ///
+    /// ```rust
+    /// use squeak::{EventBus, Response};
+    ///
+    /// struct DamageReceived(u32);
+    ///
+    /// let events = EventBus::new();
+    /// let subscription = events.subscribe(|event: &DamageReceived| {
+    ///     println!("Received {} damage", event.0);
+    ///     Response::StaySubscribed
+    /// });
+    /// events.unsubscribe::<DamageReceived>(subscription);
+    /// events.emit(DamageReceived(5)); // Does not print anything
+    /// ```
+    pub fn unsubscribe<E: 'static>(&self, subscription: Subscription) {
+        self.delegate::<E>().unsubscribe(subscription);
+    }
+
+    /// Executes all callbacks registered for events of type `E`, providing `event` as their argument.
+    ///
+    /// ```rust
+    /// use squeak::{EventBus, Response};
+    ///
+    /// struct DamageReceived(u32);
+    ///
+    /// let events = EventBus::new();
+    /// events.subscribe(|event: &DamageReceived| {
+    ///     println!("Received {} damage", event.0);
+    ///     Response::StaySubscribed
+    /// });
+    /// events.emit(DamageReceived(5)); // Prints "Received 5 damage"
+    /// ```
+    pub fn emit<E: 'static, U: Borrow<E>>(&self, event: U) {
+        self.delegate::<E>().broadcast_borrowed(event.borrow());
+    }
+}
+
+impl Debug for EventBus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> alloc::fmt::Result {
+        f.debug_struct("EventBus")
+            .field(
+                "delegates",
+                &format_args!("{} event types", self.delegates.borrow().len()),
+            )
+            .finish()
+    }
+}
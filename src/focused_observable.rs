@@ -0,0 +1,97 @@
+use core::ops::Deref;
+
+use crate::{Lens, Observable};
+
+/// View of a part of the value contained in an [`Observable`], created via
+/// [`Observable::focus`].
+///
+/// Mutations made through a focused observable only affect the part of the value selected by
+/// its [`Lens`], but subscribers of the observable receive the entire value.
+///
+/// ```rust
+/// use squeak::{lens, Observable, Response};
+///
+/// struct Player {
+///     name: String,
+///     health: u32,
+/// }
+///
+/// let mut player = Observable::new(Player { name: String::from("Lisa"), health: 100 });
+/// player.subscribe(|p| {
+///     println!("{} has {} health", p.name, p.health);
+///     Response::StaySubscribed
+/// });
+///
+/// let mut health = player.focus(lens!(Player, health));
+/// health.mutate(|h| *h -= 10); // Prints "Lisa has 90 health"
+/// ```
+pub struct FocusedObservable<'a, 'o, T, V> {
+    observable: &'a mut Observable<'o, T>,
+    lens: Lens<'a, T, V>,
+}
+
+impl<'a, 'o, T, V> FocusedObservable<'a, 'o, T, V> {
+    pub(crate) fn new(observable: &'a mut Observable<'o, T>, lens: Lens<'a, T, V>) -> Self {
+        Self { observable, lens }
+    }
+
+    /// Execute a function which may mutate the focused part of the value contained in the
+    /// observable. Subscribers of the observable are notified like with [`Observable::mutate`].
+    ///
+    /// ```rust
+    /// use squeak::{lens, Observable};
+    ///
+    /// struct Player {
+    ///     health: u32,
+    /// }
+    ///
+    /// let mut player = Observable::new(Player { health: 100 });
+    /// player.focus(lens!(Player, health)).mutate(|h| *h -= 10);
+    /// assert_eq!(player.health, 90);
+    /// ```
+    pub fn mutate<M>(&mut self, mutation: M)
+    where
+        M: FnOnce(&mut V),
+    {
+        let lens = &self.lens;
+        self.observable
+            .mutate(|value| mutation(lens.get_mut(value)));
+    }
+
+    /// Narrows this view further, to a part of the focused value. See [`Lens::then`].
+    ///
+    /// ```rust
+    /// use squeak::{lens, Observable};
+    ///
+    /// struct Stats {
+    ///     health: u32,
+    /// }
+    ///
+    /// struct Player {
+    ///     stats: Stats,
+    /// }
+    ///
+    /// let mut player = Observable::new(Player { stats: Stats { health: 100 } });
+    /// player
+    ///     .focus(lens!(Player, stats))
+    ///     .focus(lens!(Stats, health))
+    ///     .mutate(|h| *h -= 10);
+    /// assert_eq!(player.stats.health, 90);
+    /// ```
+    pub fn focus<W>(self, lens: Lens<'a, V, W>) -> FocusedObservable<'a, 'o, T, W>
+    where
+        T: 'a,
+        V: 'static,
+        W: 'a,
+    {
+        FocusedObservable::new(self.observable, self.lens.then(lens))
+    }
+}
+
+impl<T, V> Deref for FocusedObservable<'_, '_, T, V> {
+    type Target = V;
+
+    fn deref(&self) -> &Self::Target {
+        self.lens.get(self.observable)
+    }
+}
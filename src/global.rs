@@ -0,0 +1,30 @@
+use alloc::rc::Rc;
+
+use crate::{Delegate, EventBus};
+
+std::thread_local! {
+    static GLOBAL_EVENTS: EventBus = EventBus::new();
+}
+
+/// Returns the global delegate used to broadcast events of type `E`, creating it if needed.
+///
+/// This allows any module to subscribe to or broadcast cross-cutting events without passing
+/// delegates around. Global delegates are scoped to the current thread: subscriptions made on
+/// one thread are never executed by broadcasts from another thread.
+///
+/// This function requires the `std` feature.
+///
+/// ```rust
+/// use squeak::Response;
+///
+/// struct SettingsChanged;
+///
+/// squeak::global::<SettingsChanged>().subscribe(|_| {
+///     println!("Settings changed");
+///     Response::StaySubscribed
+/// });
+/// squeak::global::<SettingsChanged>().broadcast(SettingsChanged); // Prints "Settings changed"
+/// ```
+pub fn global<E: 'static>() -> Rc<Delegate<'static, E>> {
+    GLOBAL_EVENTS.with(|events| events.delegate::<E>())
+}
@@ -0,0 +1,128 @@
+use alloc::boxed::Box;
+
+type Getter<'l, T, V> = Box<dyn Fn(&T) -> &V + 'l>;
+type MutGetter<'l, T, V> = Box<dyn Fn(&mut T) -> &mut V + 'l>;
+
+/// Accessor pair pointing at a part `V` of a value `T`, used to mutate that part of the value
+/// contained in an observable via [`Observable::focus`](crate::Observable::focus).
+///
+/// Lenses to fields can be created with the [`lens!`](crate::lens!) macro:
+///
+/// ```rust
+/// use squeak::lens;
+///
+/// struct Stats {
+///     health: u32,
+/// }
+///
+/// struct Player {
+///     stats: Stats,
+/// }
+///
+/// let health = lens!(Player, stats.health);
+/// let player = Player { stats: Stats { health: 100 } };
+/// assert_eq!(*health.get(&player), 100);
+/// ```
+pub struct Lens<'l, T, V> {
+    get: Getter<'l, T, V>,
+    get_mut: MutGetter<'l, T, V>,
+}
+
+impl<'l, T, V> Lens<'l, T, V> {
+    /// Creates a lens from a pair of functions returning the same part of a value.
+    ///
+    /// ```rust
+    /// use squeak::Lens;
+    ///
+    /// let first = Lens::new(|pair: &(u32, char)| &pair.0, |pair| &mut pair.0);
+    /// assert_eq!(*first.get(&(5, 'a')), 5);
+    /// ```
+    pub fn new<G, M>(get: G, get_mut: M) -> Self
+    where
+        G: Fn(&T) -> &V + 'l,
+        M: Fn(&mut T) -> &mut V + 'l,
+    {
+        Self {
+            get: Box::new(get),
+            get_mut: Box::new(get_mut),
+        }
+    }
+
+    /// Returns the part of `value` this lens points at.
+    ///
+    /// ```rust
+    /// use squeak::Lens;
+    ///
+    /// let first = Lens::new(|pair: &(u32, char)| &pair.0, |pair| &mut pair.0);
+    /// assert_eq!(*first.get(&(5, 'a')), 5);
+    /// ```
+    pub fn get<'a>(&self, value: &'a T) -> &'a V {
+        (self.get)(value)
+    }
+
+    /// Returns the part of `value` this lens points at, mutably.
+    ///
+    /// ```rust
+    /// use squeak::Lens;
+    ///
+    /// let first = Lens::new(|pair: &(u32, char)| &pair.0, |pair| &mut pair.0);
+    /// let mut pair = (5, 'a');
+    /// *first.get_mut(&mut pair) += 1;
+    /// assert_eq!(pair, (6, 'a'));
+    /// ```
+    pub fn get_mut<'a>(&self, value: &'a mut T) -> &'a mut V {
+        (self.get_mut)(value)
+    }
+
+    /// Combines this lens with a lens pointing at a part of `V`. The intermediate type `V`
+    /// cannot contain non-static references.
+    ///
+    /// ```rust
+    /// use squeak::Lens;
+    ///
+    /// let outer = Lens::new(|t: &((u32, char), bool)| &t.0, |t| &mut t.0);
+    /// let inner = Lens::new(|t: &(u32, char)| &t.1, |t| &mut t.1);
+    /// let letter = outer.then(inner);
+    /// assert_eq!(*letter.get(&((5, 'a'), true)), 'a');
+    /// ```
+    pub fn then<W>(self, lens: Lens<'l, V, W>) -> Lens<'l, T, W>
+    where
+        T: 'l,
+        V: 'static,
+        W: 'l,
+    {
+        let Lens { get, get_mut } = self;
+        let Lens {
+            get: then_get,
+            get_mut: then_get_mut,
+        } = lens;
+        Lens::new(
+            move |value: &T| then_get(get(value)),
+            move |value: &mut T| then_get_mut(get_mut(value)),
+        )
+    }
+}
+
+/// Creates a [`Lens`] pointing at a (possibly nested) field of a type.
+///
+/// ```rust
+/// use squeak::{lens, Observable};
+///
+/// struct Position {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// let mut position = Observable::new(Position { x: 0, y: 0 });
+/// position.focus(lens!(Position, x)).mutate(|x| *x += 5);
+/// assert_eq!(position.x, 5);
+/// ```
+#[macro_export]
+macro_rules! lens {
+    ($type:ty, $($field:tt).+) => {
+        $crate::Lens::new(
+            |value: &$type| &value.$($field).+,
+            |value: &mut $type| &mut value.$($field).+,
+        )
+    };
+}
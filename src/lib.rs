@@ -42,9 +42,60 @@
 //!
 #![no_std]
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
+mod borrowed_observable;
+mod cached_value;
+mod cancellation_token;
+#[cfg(feature = "std")]
+mod chart_buffer;
+mod contextual_observable;
+mod dedup_delegate;
 mod delegate;
+mod event_bus;
+mod focused_observable;
+#[cfg(feature = "std")]
+mod global;
+mod lens;
+mod mapped_observable;
 mod observable;
+mod observable_builder;
+mod read_only_observable;
+mod replaying_observable;
+mod shared_observable;
+mod transaction;
+mod weak_delegate;
+mod zipped_observable;
 
-pub use delegate::{Delegate, Response, Subscription};
-pub use observable::Observable;
+pub use borrowed_observable::BorrowedObservable;
+pub use cached_value::CachedValue;
+pub use cancellation_token::CancellationToken;
+#[cfg(feature = "std")]
+pub use chart_buffer::ChartBuffer;
+pub use contextual_observable::ContextualObservable;
+pub use dedup_delegate::DedupDelegate;
+#[cfg(feature = "std")]
+pub use delegate::BroadcastProfile;
+pub use delegate::{
+    BoxedCallback, BroadcastError, BroadcastIter, Delegate, DelegateHandle, Handled,
+    OwnedSubscription, Response, Subscription, SubscriptionControl, SubscriptionMeta,
+};
+pub use event_bus::EventBus;
+pub use focused_observable::FocusedObservable;
+#[cfg(feature = "std")]
+pub use global::global;
+pub use lens::Lens;
+pub use mapped_observable::MappedObservable;
+#[cfg(feature = "std")]
+pub use observable::ChangeRecord;
+pub use observable::{
+    CollectionDiff, CrossDirection, IndexGuard, Observable, SaturatingArithmetic, SubscribeOptions,
+};
+pub use observable_builder::ObservableBuilder;
+pub use read_only_observable::ReadOnlyObservable;
+pub use replaying_observable::ReplayingObservable;
+pub use shared_observable::SharedObservable;
+pub use transaction::Transaction;
+pub use weak_delegate::WeakDelegate;
+pub use zipped_observable::ZippedObservable;
@@ -0,0 +1,95 @@
+use alloc::sync::Arc;
+
+use crate::{Observable, Response, Subscription};
+
+type Projection<'o, T, V> = Arc<dyn Fn(&T) -> &V + 'o + Send + Sync>;
+
+/// Read-only view of a part of the value contained in an [`Observable`], created via
+/// [`Observable::map_ref`].
+///
+/// Subscribers of the view receive a reference to the projected part of the value, computed
+/// during each broadcast of the observable. Nothing is cloned. The reference is only valid for
+/// the duration of the callback: like with regular subscriptions, callbacks which need to keep
+/// the value around must copy it.
+///
+/// ```rust
+/// use squeak::{Observable, Response};
+///
+/// struct Inventory {
+///     items: Vec<String>,
+///     gold: u32,
+/// }
+///
+/// let mut inventory = Observable::new(Inventory { items: Vec::new(), gold: 0 });
+/// inventory.map_ref(|i| &i.items).subscribe(|items| {
+///     println!("Carrying {} items", items.len());
+///     Response::StaySubscribed
+/// });
+///
+/// inventory.mutate(|i| i.items.push(String::from("Sword"))); // Prints "Carrying 1 items"
+/// ```
+pub struct MappedObservable<'a, 'o, T, V: ?Sized> {
+    observable: &'a Observable<'o, T>,
+    project: Projection<'o, T, V>,
+}
+
+impl<'a, 'o, T: 'o, V: ?Sized + 'o> MappedObservable<'a, 'o, T, V> {
+    pub(crate) fn new<F>(observable: &'a Observable<'o, T>, project: F) -> Self
+    where
+        F: Fn(&T) -> &V + 'o + Send + Sync,
+    {
+        Self {
+            observable,
+            project: Arc::new(project),
+        }
+    }
+
+    /// Registers a new callback that will be called with the projected part of the value
+    /// contained in the observable, whenever it is mutated.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// let mut position = Observable::new((0, 0));
+    /// position.map_ref(|(x, _)| x).subscribe(|x| {
+    ///     println!("X is now {x}");
+    ///     Response::StaySubscribed
+    /// });
+    /// position.mutate(|p| p.0 = 5); // Prints "X is now 5"
+    /// ```
+    pub fn subscribe<C: FnMut(&V) -> Response + 'o + Send>(&self, mut callback: C) -> Subscription {
+        let project = self.project.clone();
+        self.observable
+            .subscribe(move |value| callback(project(value)))
+    }
+
+    /// Removes a callback that was previously registered.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// let position = Observable::new((0, 0));
+    /// let x = position.map_ref(|(x, _)| x);
+    /// let subscription = x.subscribe(|x| {
+    ///     println!("X is now {x}");
+    ///     Response::StaySubscribed
+    /// });
+    /// x.unsubscribe(subscription);
+    /// ```
+    pub fn unsubscribe(&self, subscription: Subscription) {
+        self.observable.unsubscribe(subscription);
+    }
+
+    /// Returns a reference to the projected part of the value contained in the observable.
+    ///
+    /// ```rust
+    /// use squeak::Observable;
+    ///
+    /// let position = Observable::new((3, 4));
+    /// assert_eq!(*position.map_ref(|(_, y)| y).peek(), 4);
+    /// ```
+    pub fn peek(&self) -> &'a V {
+        let value: &'a T = self.observable;
+        (self.project)(value)
+    }
+}
@@ -1,7 +1,28 @@
-use alloc::fmt::Debug;
-use core::ops::Deref;
+use alloc::boxed::Box;
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::fmt::{Debug, Formatter};
+#[cfg(feature = "log")]
+use alloc::format;
+#[cfg(feature = "std")]
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::any::Any;
+use core::cell::{Cell, Ref, RefCell};
+use core::cmp::Ordering;
+use core::hash::{Hash, Hasher};
+use core::ops::{AddAssign, Deref, DerefMut, DivAssign, IndexMut, MulAssign, Sub, SubAssign};
+#[cfg(feature = "std")]
+use std::sync::{Mutex, PoisonError};
+#[cfg(feature = "std")]
+use std::time::Instant;
 
-use crate::{Delegate, Response, Subscription};
+#[cfg(feature = "std")]
+use crate::ChartBuffer;
+use crate::{
+    CachedValue, CancellationToken, ContextualObservable, Delegate, FocusedObservable, Lens,
+    MappedObservable, ObservableBuilder, ReadOnlyObservable, Response, Subscription,
+    SubscriptionControl, ZippedObservable,
+};
 
 /// Wrapper type which owns a value and executes callbacks every time a call is made to mutate the value.
 ///
@@ -20,11 +41,414 @@ use crate::{Delegate, Response, Subscription};
 /// ```
 ///
 /// Observables implement [`std::ops::Deref`], which means the inner value can be accessed
-/// via `*my_observable`.
+/// via `*my_observable`. Methods of the inner value can also be called directly on the observable,
+/// like `my_observable.len()` for an `Observable<Vec<u8>>`.
+///
+/// # Thread safety
+///
+/// An observable is [`Send`] when `T` is [`Send`]. Like [`Delegate`], observables are never [`Sync`]:
+///
+/// ```compile_fail
+/// fn assert_sync<T: Sync>() {}
+/// assert_sync::<squeak::Observable<u32>>();
+/// ```
 #[derive(Debug)]
 pub struct Observable<'o, T> {
     value: T,
+    observers: Observers<'o, T>,
+}
+
+type Validator<'a, T> = Box<dyn Fn(&mut T) + 'a + Send>;
+
+/// Everything an observable needs to notify subscribers, independently of where its value is stored.
+pub(crate) struct Observers<'o, T> {
     delegate: Delegate<'o, T>,
+    // Allocated on first use, most observables only have regular subscribers
+    extra: RefCell<Option<Box<ExtraObservers<'o, T>>>>,
+    pending: Cell<bool>,
+    validator: Option<Validator<'o, T>>,
+    armed: bool,
+    version: u64,
+    #[cfg(feature = "std")]
+    change_log: Option<Box<dyn ChangeLog<T> + 'o + Send>>,
+}
+
+struct ExtraObservers<'o, T> {
+    coalesced: Delegate<'o, T>,
+    sampled: Delegate<'o, T>,
+    pre_hooks: Delegate<'o, T>,
+    post_hooks: Delegate<'o, T>,
+}
+
+impl<'o, T> ExtraObservers<'o, T> {
+    fn new() -> Self {
+        Self {
+            coalesced: Delegate::new(),
+            sampled: Delegate::new(),
+            pre_hooks: Delegate::new(),
+            post_hooks: Delegate::new(),
+        }
+    }
+
+    fn delegates(&self) -> [&Delegate<'o, T>; 4] {
+        [
+            &self.coalesced,
+            &self.sampled,
+            &self.pre_hooks,
+            &self.post_hooks,
+        ]
+    }
+}
+
+// Broadcast context of the notifications sent by observables
+struct MutationContext {
+    version: u64,
+    tag: Option<Box<dyn Any>>,
+}
+
+impl<'o, T> Observers<'o, T> {
+    fn new() -> Self {
+        Self {
+            delegate: Delegate::new(),
+            extra: RefCell::new(None),
+            pending: Cell::new(false),
+            validator: None,
+            armed: true,
+            version: 0,
+            #[cfg(feature = "std")]
+            change_log: None,
+        }
+    }
+
+    fn extra(&self) -> Ref<'_, ExtraObservers<'o, T>> {
+        if self.extra.borrow().is_none() {
+            self.extra.replace(Some(Box::new(ExtraObservers::new())));
+        }
+        Ref::map(self.extra.borrow(), |extra| extra.as_deref().unwrap())
+    }
+
+    pub(crate) fn begin_mutation(&self, value: &T) {
+        if let Some(extra) = self.extra.borrow().as_deref() {
+            extra.pre_hooks.broadcast_borrowed(value);
+        }
+    }
+
+    pub(crate) fn end_mutation(&mut self, value: &mut T) {
+        if let Some(validator) = &self.validator {
+            validator(value);
+        }
+        self.version += 1;
+        #[cfg(feature = "std")]
+        if let Some(change_log) = self.change_log.as_mut() {
+            change_log.record(value);
+        }
+        if let Some(extra) = self.extra.get_mut().as_deref() {
+            extra.post_hooks.broadcast_borrowed(value);
+        }
+    }
+
+    // Ends a mutation which did not go through, without counting it as a change.
+    pub(crate) fn abort_mutation(&self, value: &T) {
+        if let Some(extra) = self.extra.borrow().as_deref() {
+            extra.post_hooks.broadcast_borrowed(value);
+        }
+    }
+
+    pub(crate) fn notify(&self, value: &T) {
+        self.notify_tagged(value, None);
+    }
+
+    pub(crate) fn notify_with_context<Ctx: 'static>(&self, value: &T, context: Ctx) {
+        self.notify_tagged(value, Some(Box::new(context)));
+    }
+
+    fn notify_tagged(&self, value: &T, tag: Option<Box<dyn Any>>) {
+        if !self.armed {
+            return;
+        }
+        let context = MutationContext {
+            version: self.version,
+            tag,
+        };
+        self.delegate.broadcast_with_context(value, &context);
+        self.pending.set(true);
+    }
+
+    pub(crate) fn unsubscribe(&self, subscription: Subscription) {
+        if let Some(extra) = self.extra.borrow().as_deref() {
+            let delegates = extra.delegates();
+            if let Some(delegate) = delegates.iter().find(|d| d.is_subscribed(&subscription)) {
+                delegate.unsubscribe(subscription);
+                return;
+            }
+        }
+        self.delegate.unsubscribe(subscription);
+    }
+
+    pub(crate) fn subscriber_count(&self) -> usize {
+        let extra = self.extra.borrow();
+        self.delegate.subscriber_count()
+            + extra.as_deref().map_or(0, |extra| {
+                extra.coalesced.subscriber_count() + extra.sampled.subscriber_count()
+            })
+    }
+
+    pub(crate) fn estimated_size(&self) -> usize {
+        let delegate_size = core::mem::size_of::<Delegate<T>>();
+        let extra_size = self.extra.borrow().as_deref().map_or(0, |extra| {
+            core::mem::size_of::<ExtraObservers<T>>()
+                + extra
+                    .delegates()
+                    .iter()
+                    .map(|d| d.estimated_size() - delegate_size)
+                    .sum::<usize>()
+        });
+        core::mem::size_of::<Self>() + self.delegate.estimated_size() - delegate_size + extra_size
+    }
+
+    pub(crate) fn pump(&self, value: &T) {
+        if self.pending.replace(false) {
+            if let Some(extra) = self.extra.borrow().as_deref() {
+                extra.coalesced.broadcast_borrowed(value);
+            }
+        }
+    }
+
+    pub(crate) fn sample(&self, value: &T) {
+        if let Some(extra) = self.extra.borrow().as_deref() {
+            extra.sampled.broadcast_borrowed(value);
+        }
+    }
+}
+
+impl<T> Debug for Observers<'_, T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> alloc::fmt::Result {
+        let extra = self.extra.borrow();
+        let extra = extra.as_deref();
+        f.debug_struct("Observers")
+            .field("delegate", &self.delegate)
+            .field("coalesced", &extra.map(|extra| &extra.coalesced))
+            .field("sampled", &extra.map(|extra| &extra.sampled))
+            .field("pending", &self.pending)
+            .field("validator", &self.validator.is_some())
+            .field("armed", &self.armed)
+            .field("pre_hooks", &extra.map(|extra| &extra.pre_hooks))
+            .field("post_hooks", &extra.map(|extra| &extra.post_hooks))
+            .field("version", &self.version)
+            .finish()
+    }
+}
+
+/// Entry of the change log of an [`Observable`], see [`Observable::change_log`].
+///
+/// This type requires the `std` feature.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChangeRecord<T> {
+    /// Value contained in the observable after the change.
+    pub value: T,
+    /// Time at which the change happened.
+    pub at: Instant,
+}
+
+#[cfg(feature = "std")]
+trait ChangeLog<T> {
+    fn record(&mut self, value: &T);
+    fn records(&self) -> &[ChangeRecord<T>];
+}
+
+#[cfg(feature = "std")]
+struct BoundedChangeLog<T> {
+    capacity: usize,
+    records: Vec<ChangeRecord<T>>,
+}
+
+#[cfg(feature = "std")]
+impl<T: Clone> ChangeLog<T> for BoundedChangeLog<T> {
+    fn record(&mut self, value: &T) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.records.len() == self.capacity {
+            self.records.remove(0);
+        }
+        self.records.push(ChangeRecord {
+            value: value.clone(),
+            at: Instant::now(),
+        });
+    }
+
+    fn records(&self) -> &[ChangeRecord<T>] {
+        &self.records
+    }
+}
+
+/// Direction in which a value must cross a threshold to execute callbacks registered via
+/// [`Observable::subscribe_threshold`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CrossDirection {
+    /// The value went from below the threshold to greater than or equal to the threshold.
+    Rising,
+    /// The value went from greater than or equal to the threshold to below the threshold.
+    Falling,
+}
+
+/// Integer types supporting the saturating arithmetic used by
+/// [`Observable::mutate_saturating_add`] and [`Observable::mutate_saturating_sub`].
+pub trait SaturatingArithmetic: Sized {
+    /// Computes `self + rhs`, clamping at the numeric bounds instead of overflowing.
+    fn saturating_add(self, rhs: Self) -> Self;
+    /// Computes `self - rhs`, clamping at the numeric bounds instead of overflowing.
+    fn saturating_sub(self, rhs: Self) -> Self;
+}
+
+macro_rules! impl_saturating_arithmetic {
+    ($($t:ty),*) => {
+        $(
+            impl SaturatingArithmetic for $t {
+                fn saturating_add(self, rhs: Self) -> Self {
+                    <$t>::saturating_add(self, rhs)
+                }
+
+                fn saturating_sub(self, rhs: Self) -> Self {
+                    <$t>::saturating_sub(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_saturating_arithmetic!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// Provides mutable access to one element of the value contained in an [`Observable`].
+/// Subscription callbacks are executed with the whole value when the guard is dropped.
+///
+/// Created via [`Observable::get_index_mut`].
+pub struct IndexGuard<'a, 'o, T: IndexMut<I>, I: Clone> {
+    observable: &'a mut Observable<'o, T>,
+    index: I,
+}
+
+impl<T: IndexMut<I>, I: Clone> Deref for IndexGuard<'_, '_, T, I> {
+    type Target = T::Output;
+    fn deref(&self) -> &Self::Target {
+        &self.observable.value[self.index.clone()]
+    }
+}
+
+impl<T: IndexMut<I>, I: Clone> DerefMut for IndexGuard<'_, '_, T, I> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.observable.value[self.index.clone()]
+    }
+}
+
+impl<T: IndexMut<I>, I: Clone> Drop for IndexGuard<'_, '_, T, I> {
+    fn drop(&mut self) {
+        let observable = &mut *self.observable;
+        observable.observers.end_mutation(&mut observable.value);
+        observable.observers.notify(&observable.value);
+    }
+}
+
+/// Options for callbacks registered via [`Observable::subscribe_opts`].
+///
+/// ```rust
+/// use squeak::SubscribeOptions;
+///
+/// let options = SubscribeOptions {
+///     fire_immediately: true,
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct SubscribeOptions {
+    /// Executes the callback with the current value as soon as it is registered.
+    pub fire_immediately: bool,
+    /// Callbacks with higher priorities are executed before callbacks with lower priorities.
+    /// Callbacks with equal priorities are executed in the order they were registered.
+    /// Defaults to zero, the priority of callbacks registered via other methods.
+    pub priority: i32,
+    /// Cancels the subscription after the callback has been executed once.
+    pub once: bool,
+}
+
+/// Changes between two versions of a collection, delivered to callbacks registered via
+/// [`Observable::subscribe_diff`] or [`Observable::subscribe_diff_by_key`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CollectionDiff<T> {
+    /// Elements which only exist in the new collection, with their index in the new collection.
+    pub added: Vec<(usize, T)>,
+    /// Elements which only exist in the old collection, with their index in the old collection.
+    pub removed: Vec<(usize, T)>,
+    /// Elements which exist in both collections with different values, with their index in the
+    /// new collection, their old value and their new value.
+    pub changed: Vec<(usize, T, T)>,
+}
+
+impl<T: Clone + PartialEq> CollectionDiff<T> {
+    /// Returns whether the two collections were identical.
+    ///
+    /// ```rust
+    /// use squeak::CollectionDiff;
+    ///
+    /// assert!(CollectionDiff::<u32>::default().is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    fn by_index(old: &[T], new: &[T]) -> Self {
+        let mut diff = Self {
+            added: Vec::new(),
+            removed: Vec::new(),
+            changed: Vec::new(),
+        };
+        for (index, (old_element, new_element)) in old.iter().zip(new).enumerate() {
+            if old_element != new_element {
+                diff.changed
+                    .push((index, old_element.clone(), new_element.clone()));
+            }
+        }
+        diff.added
+            .extend(new.iter().cloned().enumerate().skip(old.len()));
+        diff.removed
+            .extend(old.iter().cloned().enumerate().skip(new.len()));
+        diff
+    }
+
+    fn by_key<K: Ord, F: Fn(&T) -> K>(old: &[T], new: &[T], key: F) -> Self {
+        let old_by_key: BTreeMap<K, (usize, &T)> = old
+            .iter()
+            .enumerate()
+            .map(|(index, element)| (key(element), (index, element)))
+            .collect();
+        let new_keys: BTreeSet<K> = new.iter().map(&key).collect();
+        let mut diff = Self {
+            added: Vec::new(),
+            removed: Vec::new(),
+            changed: Vec::new(),
+        };
+        for (index, new_element) in new.iter().enumerate() {
+            match old_by_key.get(&key(new_element)) {
+                None => diff.added.push((index, new_element.clone())),
+                Some((_, old_element)) if *old_element != new_element => {
+                    diff.changed
+                        .push((index, (*old_element).clone(), new_element.clone()))
+                }
+                Some(_) => (),
+            }
+        }
+        for (key, (index, old_element)) in old_by_key {
+            if !new_keys.contains(&key) {
+                diff.removed.push((index, old_element.clone()));
+            }
+        }
+        diff.removed.sort_by_key(|(index, _)| *index);
+        diff
+    }
 }
 
 impl<'o, T> Observable<'o, T> {
@@ -37,12 +461,124 @@ impl<'o, T> Observable<'o, T> {
     pub fn new(value: T) -> Self {
         Self {
             value,
-            delegate: Delegate {
-                subscriptions: Default::default(),
-            },
+            observers: Observers::new(),
         }
     }
 
+    /// Returns a builder which configures an observable with an initial value before creating it.
+    /// See [`ObservableBuilder`] for the available options.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// let (health, subscriptions) = Observable::builder(100)
+    ///     .subscribe(|updated_health| {
+    ///         println!("Health is now {updated_health}");
+    ///         Response::StaySubscribed
+    ///     })
+    ///     .build();
+    /// ```
+    pub fn builder(value: T) -> ObservableBuilder<'o, T> {
+        ObservableBuilder::new(value)
+    }
+
+    /// Creates a new observable with an initial value and a context, which will be passed to
+    /// callbacks registered via [`ContextualObservable::subscribe_with_context`].
+    ///
+    /// ```rust
+    /// use squeak::Observable;
+    /// let health = Observable::with_context(100, "Health");
+    /// ```
+    pub fn with_context<C>(value: T, context: C) -> ContextualObservable<'o, T, C> {
+        ContextualObservable::new(value, context)
+    }
+
+    /// Creates a new observable which does not execute subscription callbacks until
+    /// [`Observable::arm`] is called. Until then, mutations update the value silently.
+    ///
+    /// This is useful when subscribing to an observable while the state touched by callbacks
+    /// is not initialized yet. Methods which execute a callback at subscription time,
+    /// such as [`Observable::subscribe_with_snapshot`], are not affected.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// let mut health = Observable::new_disarmed(100);
+    /// health.subscribe(|updated_health| {
+    ///     println!("Health is now {updated_health}");
+    ///     Response::StaySubscribed
+    /// });
+    ///
+    /// health.mutate(|h| *h -= 10); // Does not print anything
+    /// health.arm();
+    /// health.notify_current(); // Prints "Health is now 90"
+    /// health.mutate(|h| *h -= 10); // Prints "Health is now 80"
+    /// ```
+    pub fn new_disarmed(value: T) -> Self {
+        let mut observable = Self::new(value);
+        observable.observers.armed = false;
+        observable
+    }
+
+    /// Enables execution of subscription callbacks on an observable created via
+    /// [`Observable::new_disarmed`]. Arming does not execute callbacks by itself,
+    /// use [`Observable::notify_current`] to broadcast the current value if needed.
+    ///
+    /// ```rust
+    /// use squeak::Observable;
+    ///
+    /// let mut health = Observable::new_disarmed(100);
+    /// health.arm();
+    /// ```
+    pub fn arm(&mut self) {
+        self.observers.armed = true;
+    }
+
+    /// Creates a new observable with an initial value and a validator. The validator is applied to
+    /// the initial value, and after every mutation before subscription callbacks are executed.
+    /// This can be used to enforce invariants on the value, such as keeping it within a range.
+    ///
+    /// Clones of this observable do not carry the validator.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// let mut volume = Observable::with_validator(50, |v: &mut u32| *v = (*v).min(100));
+    /// volume.subscribe(|updated_volume| {
+    ///     println!("Volume is now {updated_volume}");
+    ///     Response::StaySubscribed
+    /// });
+    ///
+    /// volume.mutate(|v| *v += 80); // Prints "Volume is now 100"
+    /// ```
+    pub fn with_validator<V: Fn(&mut T) + 'o + Send>(mut value: T, validator: V) -> Self {
+        validator(&mut value);
+        let mut observers = Observers::new();
+        observers.validator = Some(Box::new(validator));
+        Self { value, observers }
+    }
+
+    pub(crate) fn from_parts(value: T, observers: Observers<'o, T>) -> Self {
+        Self { value, observers }
+    }
+
+    pub(crate) fn into_parts(self) -> (T, Observers<'o, T>) {
+        (self.value, self.observers)
+    }
+
+    /// Consumes the observable and returns the value it contains.
+    /// Any active subscription is dropped without being notified.
+    ///
+    /// ```rust
+    /// use squeak::Observable;
+    ///
+    /// let name = Observable::new(String::from("DefaultName"));
+    /// assert_eq!(name.into_inner(), "DefaultName");
+    /// ```
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
     /// Registers a new callback that will be called when the value contained in this observable is mutated.
     ///
     /// ```rust
@@ -58,85 +594,1942 @@ impl<'o, T> Observable<'o, T> {
     /// The output of the callback function determines whether it will be called
     /// again when [`broadcast`] is called in the future.
     pub fn subscribe<C: FnMut(&T) -> Response + 'o + Send>(&self, callback: C) -> Subscription {
-        self.delegate.subscribe(callback)
+        self.observers.delegate.subscribe(callback)
     }
 
-    /// Removes a callback that was previously registered.
+    /// Registers a new callback that will be called when the value contained in this observable is mutated,
+    /// with behaviors selected via `options`.
     ///
     /// ```rust
-    /// use squeak::{Observable, Response};
+    /// use squeak::{Observable, Response, SubscribeOptions};
     ///
     /// let mut health = Observable::new(100);
-    /// let subscription = health.subscribe(|updated_health| {
+    /// let options = SubscribeOptions {
+    ///     fire_immediately: true,
+    ///     once: true,
+    ///     ..Default::default()
+    /// };
+    /// health.subscribe_opts(options, |updated_health| {
     ///     println!("Health is now {updated_health}");
     ///     Response::StaySubscribed
-    /// });
+    /// }); // Prints "Health is now 100"
     ///
-    /// health.unsubscribe(subscription);
+    /// health.mutate(|h| *h -= 10); // Does not print anything
     /// ```
-    pub fn unsubscribe(&self, subscription: Subscription) {
-        self.delegate.unsubscribe(subscription);
+    pub fn subscribe_opts<C: FnMut(&T) -> Response + 'o + Send>(
+        &self,
+        options: SubscribeOptions,
+        mut callback: C,
+    ) -> Subscription {
+        if options.fire_immediately {
+            let response = callback(&self.value);
+            if options.once || response.is_cancellation() {
+                return Subscription::new();
+            }
+        }
+        let delegate = &self.observers.delegate;
+        match options.once {
+            true => delegate.subscribe_prioritized(options.priority, move |value| {
+                callback(value);
+                Response::CancelSubscription
+            }),
+            false => delegate.subscribe_prioritized(options.priority, callback),
+        }
     }
 
-    /// Returns a reference to a delegate that will execute subscription functions
-    /// when the observable is mutated. This is useful when writing a struct that has
-    /// an observable member, but users of the struct should only have access to its
-    /// value by subscribing.
+    /// Registers a new callback that will be called when the value contained in this observable is mutated
+    /// via [`Observable::mutate_tagged`] with a tag of type `Tag`. The callback also receives the tag.
+    /// Other mutations do not execute the callback.
     ///
     /// ```rust
-    /// use squeak::{Delegate, Observable};
+    /// use squeak::{Observable, Response};
     ///
-    /// struct MyStruct<'a> {
-    ///   observe_only: Observable<'a, u32>,
+    /// #[derive(Debug)]
+    /// enum Cause {
+    ///     Player,
+    ///     Script,
     /// }
     ///
-    /// impl<'a> MyStruct<'a> {
-    ///   pub fn delegate(&self) -> &Delegate<'a, u32> {
-    ///     self.observe_only.delegate()
-    ///   }
-    /// }
+    /// let mut name = Observable::new(String::from("Lisa"));
+    /// name.subscribe_tagged_changes(|cause: &Cause, new_name| {
+    ///     println!("{cause:?} renamed to {new_name}");
+    ///     Response::StaySubscribed
+    /// });
+    /// name.mutate_tagged(Cause::Player, |n| *n = String::from("Bart")); // Prints "Player renamed to Bart"
+    /// name.mutate(|n| *n = String::from("Maggie")); // Does not print anything
+    /// ```
+    pub fn subscribe_tagged_changes<Tag, C>(&self, mut callback: C) -> Subscription
+    where
+        Tag: 'static,
+        C: FnMut(&Tag, &T) -> Response + 'o + Send,
+    {
+        self.observers.delegate.subscribe_with_broadcast_context(
+            move |context: &MutationContext, value| match context
+                .tag
+                .as_deref()
+                .and_then(|tag| tag.downcast_ref::<Tag>())
+            {
+                Some(tag) => callback(tag, value),
+                None => Response::StaySubscribed,
+            },
+        )
+    }
+
+    /// Registers a new callback that will be called when the value contained in this observable is mutated.
+    /// The callback also receives the number of mutations made so far, see [`Observable::version`].
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
     ///
+    /// let mut health = Observable::new(100);
+    /// health.subscribe_with_version(|version, updated_health| {
+    ///     println!("Health is now {updated_health} (version {version})");
+    ///     Response::StaySubscribed
+    /// });
+    /// health.mutate(|h| *h -= 10); // Prints "Health is now 90 (version 1)"
+    /// health.mutate(|h| *h -= 10); // Prints "Health is now 80 (version 2)"
     /// ```
+    pub fn subscribe_with_version<C: FnMut(u64, &T) -> Response + 'o + Send>(
+        &self,
+        mut callback: C,
+    ) -> Subscription {
+        self.observers.delegate.subscribe_with_broadcast_context(
+            move |context: &MutationContext, value| callback(context.version, value),
+        )
+    }
+
+    /// Returns how many times the value contained in this observable was mutated. Mutations which
+    /// do not execute subscription callbacks, like [`Observable::set_silently`], are counted too.
     ///
-    pub fn delegate(&self) -> &Delegate<'o, T> {
-        &self.delegate
+    /// ```rust
+    /// use squeak::Observable;
+    ///
+    /// let mut health = Observable::new(100);
+    /// assert_eq!(health.version(), 0);
+    /// health.mutate(|h| *h -= 10);
+    /// health.set_silently(50);
+    /// assert_eq!(health.version(), 2);
+    /// ```
+    pub fn version(&self) -> u64 {
+        self.observers.version
     }
 
-    /// Execute a function which may mutate the value contained in this observable.
-    /// Subscription callbacks will be executed regardless of what happens inside
-    /// the `mutation` function.
+    /// Registers a new callback that will be called when the value contained in this observable is mutated.
+    /// Instead of returning a [`Response`], the callback controls its subscription via
+    /// the [`SubscriptionControl`] it receives.
     ///
     /// ```rust
     /// use squeak::Observable;
     ///
-    /// let mut name = Observable::new(String::from("DefaultName"));
-    /// name.mutate(|n| n.push_str("X"));
-    /// name.mutate(|n| n.push_str("Y"));
-    /// name.mutate(|n| n.push_str("Z"));
+    /// let mut health = Observable::new(100);
+    /// health.subscribe_controlled(|updated_health, control| {
+    ///     println!("Health is now {updated_health}");
+    ///     if *updated_health <= 0 {
+    ///         control.cancel();
+    ///     }
+    /// });
+    /// ```
+    pub fn subscribe_controlled<C: FnMut(&T, &mut SubscriptionControl) + 'o + Send>(
+        &self,
+        callback: C,
+    ) -> Subscription {
+        self.observers.delegate.subscribe_controlled(callback)
+    }
+
+    /// Registers a callback which observes every mutation and never cancels its subscription.
+    /// This is convenient to temporarily tap into an observable, for example to log its changes.
     ///
-    /// assert_eq!(name.as_str(), "DefaultNameXYZ");
+    /// ```rust
+    /// use squeak::Observable;
+    ///
+    /// let mut health = Observable::new(100);
+    /// let tap = health.inspect(|updated_health| println!("Health is now {updated_health}"));
+    /// health.mutate(|h| *h -= 10); // Prints "Health is now 90"
+    /// health.unsubscribe(tap);
+    /// health.mutate(|h| *h -= 10); // Does not print anything
     /// ```
-    pub fn mutate<M>(&mut self, mutation: M)
+    pub fn inspect<F: Fn(&T) + 'o + Send>(&self, f: F) -> Subscription {
+        self.observers.delegate.inspect(f)
+    }
+
+    /// Registers a callback which logs every change of the value contained in this observable,
+    /// at the debug level. Log messages include the type of the value, and its previous and
+    /// updated values.
+    ///
+    /// This method requires the `log` feature.
+    ///
+    /// ```rust
+    /// use squeak::Observable;
+    ///
+    /// let mut health = Observable::new(100);
+    /// let watch = health.watch();
+    /// health.mutate(|h| *h -= 10); // Logs "i32: 100 -> 90"
+    /// health.unsubscribe(watch);
+    /// ```
+    #[cfg(feature = "log")]
+    pub fn watch(&self) -> Subscription
     where
-        M: FnOnce(&mut T),
+        T: Debug,
     {
-        mutation(&mut self.value);
-        self.delegate.broadcast(&self.value);
+        let mut previous = format!("{:?}", self.value);
+        self.observers.delegate.subscribe(move |value| {
+            let current = format!("{value:?}");
+            log::debug!(
+                "{}: {} -> {}",
+                core::any::type_name::<T>(),
+                previous,
+                current
+            );
+            previous = current;
+            Response::StaySubscribed
+        })
     }
-}
 
-impl<T> Default for Observable<'_, T>
-where
-    T: Default,
+    /// Registers a function which will be called before every mutation of the value contained in
+    /// this observable, with the value as it was before the mutation.
+    ///
+    /// Hooks are called even when the mutation does not execute subscription callbacks,
+    /// for example when using [`Observable::set_silently`]. They can be removed via
+    /// [`Observable::unsubscribe`].
+    ///
+    /// ```rust
+    /// use squeak::Observable;
+    ///
+    /// let mut health = Observable::new(100);
+    /// health.add_pre_hook(|h| println!("Health was {h}"));
+    /// health.mutate(|h| *h -= 10); // Prints "Health was 100"
+    /// ```
+    pub fn add_pre_hook<F: Fn(&T) + 'o + Send>(&self, hook: F) -> Subscription {
+        self.observers.extra().pre_hooks.inspect(hook)
+    }
+
+    /// Registers a function which will be called after every mutation of the value contained in
+    /// this observable, before subscription callbacks are executed.
+    ///
+    /// Hooks are called even when the mutation does not execute subscription callbacks,
+    /// for example when using [`Observable::set_silently`]. They are also called when
+    /// [`Observable::try_mutate`] or [`Observable::transaction`] fail, so that every call to
+    /// hooks registered via [`Observable::add_pre_hook`] is followed by a call to post-hooks.
+    /// They can be removed via [`Observable::unsubscribe`].
+    ///
+    /// ```rust
+    /// use squeak::Observable;
+    ///
+    /// let mut health = Observable::new(100);
+    /// health.add_post_hook(|h| println!("Health became {h}"));
+    /// health.mutate(|h| *h -= 10); // Prints "Health became 90"
+    /// ```
+    pub fn add_post_hook<F: Fn(&T) + 'o + Send>(&self, hook: F) -> Subscription {
+        self.observers.extra().post_hooks.inspect(hook)
+    }
+
+    /// Registers a new callback that will be called when the value contained in this observable is mutated,
+    /// until `token` is cancelled. The subscription is removed by the first mutation following the cancellation.
+    ///
+    /// ```rust
+    /// use squeak::{CancellationToken, Observable, Response};
+    ///
+    /// let token = CancellationToken::new();
+    /// let mut health = Observable::new(100);
+    /// health.subscribe_until(&token, |updated_health| {
+    ///     println!("Health is now {updated_health}");
+    ///     Response::StaySubscribed
+    /// });
+    /// health.mutate(|h| *h -= 10); // Prints "Health is now 90"
+    /// token.cancel();
+    /// health.mutate(|h| *h -= 10); // Does not print anything
+    /// ```
+    pub fn subscribe_until<C: FnMut(&T) -> Response + 'o + Send>(
+        &self,
+        token: &CancellationToken,
+        callback: C,
+    ) -> Subscription {
+        self.observers.delegate.subscribe_until(token, callback)
+    }
+
+    /// Registers a new callback which is not executed during mutations. Instead, every time the value
+    /// contained in this observable is mutated, a task calling the callback with a copy of the new value
+    /// is handed to `post`. See [`Delegate::subscribe_on`].
+    ///
+    /// ```rust
+    /// use std::sync::mpsc;
+    /// use squeak::Observable;
+    ///
+    /// let (sender, receiver) = mpsc::channel::<Box<dyn FnOnce() + Send>>();
+    /// let mut health = Observable::new(100);
+    /// health.subscribe_on(
+    ///     move |task| sender.send(task).unwrap(),
+    ///     |updated_health: i32| println!("Health is now {updated_health}"),
+    /// );
+    ///
+    /// health.mutate(|h| *h -= 10); // Does not print anything
+    /// for task in receiver.try_iter() {
+    ///     task(); // Prints "Health is now 90"
+    /// }
+    /// ```
+    pub fn subscribe_on<P, C>(&self, post: P, callback: C) -> Subscription
+    where
+        T: Clone + Send + 'static,
+        P: Fn(Box<dyn FnOnce() + Send>) + 'o + Send,
+        C: Fn(T) + Send + Sync + 'static,
+    {
+        self.observers.delegate.subscribe_on(post, callback)
+    }
+
+    /// Registers a new callback that will be called when the value contained in this observable is mutated,
+    /// as long as `is_alive` returns `true`. The subscription is removed by the first mutation where it returns `false`.
+    ///
+    /// ```rust
+    /// use std::sync::Arc;
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    /// use squeak::{Observable, Response};
+    ///
+    /// let window_open = Arc::new(AtomicBool::new(true));
+    /// let mut health = Observable::new(100);
+    /// let window = window_open.clone();
+    /// health.subscribe_while_alive(
+    ///     move || window.load(Ordering::SeqCst),
+    ///     |updated_health| {
+    ///         println!("Health is now {updated_health}");
+    ///         Response::StaySubscribed
+    ///     },
+    /// );
+    /// health.mutate(|h| *h -= 10); // Prints "Health is now 90"
+    /// window_open.store(false, Ordering::SeqCst);
+    /// health.mutate(|h| *h -= 10); // Does not print anything
+    /// ```
+    pub fn subscribe_while_alive<A, C>(&self, is_alive: A, callback: C) -> Subscription
+    where
+        A: Fn() -> bool + 'o + Send,
+        C: FnMut(&T) -> Response + 'o + Send,
+    {
+        self.observers
+            .delegate
+            .subscribe_while_alive(is_alive, callback)
+    }
+
+    /// Registers a new callback that will be called when the value contained in this observable is mutated,
+    /// and returns a copy of the current value alongside the subscription.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// let health = Observable::new(100);
+    /// let (subscription, initial_health) = health.subscribe_with_snapshot(|updated_health| {
+    ///     println!("Health is now {updated_health}");
+    ///     Response::StaySubscribed
+    /// });
+    /// assert_eq!(initial_health, 100);
+    /// ```
+    pub fn subscribe_with_snapshot<C: FnMut(&T) -> Response + 'o + Send>(
+        &self,
+        callback: C,
+    ) -> (Subscription, T)
+    where
+        T: Clone,
+    {
+        (
+            self.observers.delegate.subscribe(callback),
+            self.value.clone(),
+        )
+    }
+
+    /// Registers a new callback that will be called the next `n` times the value contained in this
+    /// observable is mutated. The subscription is cancelled after the last call.
+    /// If `n` is zero, no callback is registered and the returned subscription is inactive.
+    ///
+    /// ```rust
+    /// use squeak::Observable;
+    ///
+    /// let mut health = Observable::new(100);
+    /// health.subscribe_times(2, |updated_health| {
+    ///     println!("Health is now {updated_health}");
+    /// });
+    ///
+    /// health.mutate(|h| *h -= 10); // Prints "Health is now 90"
+    /// health.mutate(|h| *h -= 5);  // Prints "Health is now 85"
+    /// health.mutate(|h| *h += 25); // Does not print anything
+    /// ```
+    pub fn subscribe_times<C: FnMut(&T) + 'o + Send>(
+        &self,
+        n: usize,
+        mut callback: C,
+    ) -> Subscription {
+        if n == 0 {
+            return Subscription::new();
+        }
+        let mut remaining = n;
+        self.observers.delegate.subscribe(move |value| {
+            callback(value);
+            remaining -= 1;
+            match remaining {
+                0 => Response::CancelSubscription,
+                _ => Response::StaySubscribed,
+            }
+        })
+    }
+
+    /// Registers a new callback that will be called on every `n`-th mutation of the value contained
+    /// in this observable, counting from the time of subscription. Other mutations are ignored.
+    /// If `n` is zero, no callback is registered and the returned subscription is inactive.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// let mut progress = Observable::new(0);
+    /// progress.subscribe_every_nth(10, |p| {
+    ///     println!("Progress: {p}%");
+    ///     Response::StaySubscribed
+    /// });
+    ///
+    /// for _ in 0..20 {
+    ///     progress.mutate(|p| *p += 1); // Prints "Progress: 10%", then "Progress: 20%"
+    /// }
+    /// ```
+    pub fn subscribe_every_nth<C: FnMut(&T) -> Response + 'o + Send>(
+        &self,
+        n: usize,
+        mut callback: C,
+    ) -> Subscription {
+        if n == 0 {
+            return Subscription::new();
+        }
+        let mut count = 0;
+        self.observers.delegate.subscribe(move |value| {
+            count += 1;
+            match count % n {
+                0 => callback(value),
+                _ => Response::StaySubscribed,
+            }
+        })
+    }
+
+    /// Registers a new callback that will be called when a mutation makes the value contained in this
+    /// observable cross `threshold` in the specified direction. Mutations which do not cross the
+    /// threshold are ignored.
+    ///
+    /// ```rust
+    /// use squeak::{CrossDirection, Observable, Response};
+    ///
+    /// let mut health = Observable::new(100);
+    /// health.subscribe_threshold(25, CrossDirection::Falling, |updated_health| {
+    ///     println!("Health is low ({updated_health})");
+    ///     Response::StaySubscribed
+    /// });
+    ///
+    /// health.mutate(|h| *h -= 50); // Does not print anything
+    /// health.mutate(|h| *h -= 30); // Prints "Health is low (20)"
+    /// health.mutate(|h| *h -= 10); // Does not print anything
+    /// ```
+    pub fn subscribe_threshold<C: FnMut(&T) -> Response + 'o + Send>(
+        &self,
+        threshold: T,
+        direction: CrossDirection,
+        mut callback: C,
+    ) -> Subscription
+    where
+        T: PartialOrd + Send + 'o,
+    {
+        let mut was_below = self.value < threshold;
+        self.observers.delegate.subscribe(move |value| {
+            let is_below = *value < threshold;
+            let crossed = match direction {
+                CrossDirection::Rising => was_below && !is_below,
+                CrossDirection::Falling => !was_below && is_below,
+            };
+            was_below = is_below;
+            match crossed {
+                true => callback(value),
+                false => Response::StaySubscribed,
+            }
+        })
+    }
+
+    /// Registers a new callback that will be called when a mutation moves the value contained in this
+    /// observable by at least `min_delta` away from the value the callback last received. Smaller
+    /// changes are ignored, but add up until they reach `min_delta`.
+    ///
+    /// The value held when subscribing is the starting point, so the first mutation only executes
+    /// the callback if it moves the value by at least `min_delta` from there.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// let mut temperature = Observable::new(20.0);
+    /// temperature.subscribe_on_delta(1.0, |t| {
+    ///     println!("Temperature is now {t}");
+    ///     Response::StaySubscribed
+    /// });
+    ///
+    /// temperature.mutate(|t| *t = 20.5); // Does not print anything
+    /// temperature.mutate(|t| *t = 21.0); // Prints "Temperature is now 21"
+    /// temperature.mutate(|t| *t = 20.2); // Does not print anything
+    /// ```
+    pub fn subscribe_on_delta<C: FnMut(&T) -> Response + 'o + Send>(
+        &self,
+        min_delta: T,
+        mut callback: C,
+    ) -> Subscription
+    where
+        T: Sub<Output = T> + PartialOrd + Copy + Send + 'o,
+    {
+        let mut last = self.value;
+        self.observers.delegate.subscribe(move |value| {
+            let delta = match *value >= last {
+                true => *value - last,
+                false => last - *value,
+            };
+            match delta >= min_delta {
+                true => {
+                    last = *value;
+                    callback(value)
+                }
+                false => Response::StaySubscribed,
+            }
+        })
+    }
+
+    /// Registers a new callback that will be called when a mutation changes the key computed by
+    /// `key` from the value contained in this observable. Mutations which leave the key unchanged
+    /// are ignored. The callback receives the entire value.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// struct Player {
+    ///     level: u32,
+    ///     health: u32,
+    /// }
+    ///
+    /// let mut player = Observable::new(Player { level: 1, health: 100 });
+    /// player.subscribe_on_key_change(
+    ///     |player| player.level,
+    ///     |player| {
+    ///         println!("Reached level {}", player.level);
+    ///         Response::StaySubscribed
+    ///     },
+    /// );
+    ///
+    /// player.mutate(|p| p.health -= 10); // Does not print anything
+    /// player.mutate(|p| p.level += 1); // Prints "Reached level 2"
+    /// ```
+    pub fn subscribe_on_key_change<K, F, C>(&self, key: F, mut callback: C) -> Subscription
+    where
+        K: PartialEq + Send + 'o,
+        F: Fn(&T) -> K + 'o + Send,
+        C: FnMut(&T) -> Response + 'o + Send,
+    {
+        let mut previous = key(&self.value);
+        self.observers.delegate.subscribe(move |value| {
+            let current = key(value);
+            match current != previous {
+                true => {
+                    previous = current;
+                    callback(value)
+                }
+                false => Response::StaySubscribed,
+            }
+        })
+    }
+
+    /// Registers a new callback that will be called with a value derived from the value contained in
+    /// this observable, whenever it is mutated. Mutations for which `extract` returns `None` do not
+    /// execute the callback, but keep the subscription active.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// let mut port = Observable::new(String::from("8080"));
+    /// port.subscribe_try(
+    ///     |port| port.parse::<u16>().ok(),
+    ///     |port| {
+    ///         println!("Now listening on port {port}");
+    ///         Response::StaySubscribed
+    ///     },
+    /// );
+    ///
+    /// port.mutate(|p| *p = String::from("abc")); // Does not print anything
+    /// port.mutate(|p| *p = String::from("3000")); // Prints "Now listening on port 3000"
+    /// ```
+    pub fn subscribe_try<V, F, C>(&self, extract: F, mut callback: C) -> Subscription
+    where
+        F: Fn(&T) -> Option<V> + 'o + Send,
+        C: FnMut(&V) -> Response + 'o + Send,
+    {
+        self.observers
+            .delegate
+            .subscribe(move |value| match extract(value) {
+                Some(extracted) => callback(&extracted),
+                None => Response::StaySubscribed,
+            })
+    }
+
+    /// Registers a new callback that will be called with the value contained in this observable,
+    /// converted to `V`, whenever it is mutated.
+    ///
+    /// The value is cloned and converted on every mutation, before executing the callback.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// let mut score = Observable::new(10_i32);
+    /// score.subscribe_as_into(|score: f64| {
+    ///     println!("Score is now {:.1}", score / 3.0);
+    ///     Response::StaySubscribed
+    /// });
+    ///
+    /// score.mutate(|s| *s = 20); // Prints "Score is now 6.7"
+    /// ```
+    pub fn subscribe_as_into<V, C>(&self, mut callback: C) -> Subscription
+    where
+        T: Clone + Into<V>,
+        C: FnMut(V) -> Response + 'o + Send,
+    {
+        self.observers
+            .delegate
+            .subscribe(move |value: &T| callback(value.clone().into()))
+    }
+
+    /// Registers a new callback that will be called once, the first time a mutation makes
+    /// `predicate` return true for the value contained in this observable. Mutations which do
+    /// not satisfy the predicate are ignored.
+    ///
+    /// ```rust
+    /// use squeak::Observable;
+    ///
+    /// let mut health = Observable::new(20);
+    /// health.subscribe_until_satisfied(|h| *h <= 0, |_| println!("Game over"));
+    ///
+    /// health.mutate(|h| *h -= 10); // Does not print anything
+    /// health.mutate(|h| *h -= 10); // Prints "Game over"
+    /// health.mutate(|h| *h -= 10); // Does not print anything
+    /// ```
+    pub fn subscribe_until_satisfied<P, C>(&self, predicate: P, mut callback: C) -> Subscription
+    where
+        P: Fn(&T) -> bool + 'o + Send,
+        C: FnMut(&T) + 'o + Send,
+    {
+        self.observers.delegate.subscribe(move |value| {
+            let satisfied = predicate(value);
+            if satisfied {
+                callback(value);
+            }
+            Response::cancel_if(satisfied)
+        })
+    }
+
+    /// Returns a future which resolves with a copy of the value contained in this observable,
+    /// the first time a mutation makes `predicate` return true. If the current value already
+    /// satisfies the predicate, the future resolves immediately. If the observable is dropped
+    /// before the predicate is satisfied, the future resolves with `None`.
+    ///
+    /// This method requires the `tokio` feature.
+    ///
+    /// ```rust
+    /// use squeak::Observable;
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// let mut health = Observable::new(20);
+    /// let game_over = health.wait_for(|h| *h <= 0);
+    /// health.mutate(|h| *h -= 10);
+    /// health.mutate(|h| *h -= 10);
+    /// assert_eq!(game_over.await, Some(0));
+    /// # }
+    /// ```
+    #[cfg(feature = "tokio")]
+    pub fn wait_for<P>(&self, predicate: P) -> impl core::future::Future<Output = Option<T>>
+    where
+        T: Clone + Send + 'o,
+        P: Fn(&T) -> bool + 'o + Send,
+    {
+        let (sender, receiver) = tokio::sync::oneshot::channel();
+        if predicate(&self.value) {
+            let _ = sender.send(self.value.clone());
+        } else {
+            let mut sender = Some(sender);
+            self.subscribe_until_satisfied(predicate, move |value| {
+                if let Some(sender) = sender.take() {
+                    let _ = sender.send(value.clone());
+                }
+            });
+        }
+        async move { receiver.await.ok() }
+    }
+
+    /// Registers a new callback that will be called by [`Observable::pump`], if the value contained
+    /// in this observable was mutated since the previous pump. Intermediate values are skipped,
+    /// so the callback only ever sees the latest value.
+    ///
+    /// This is useful to decouple expensive callbacks from the rate at which the value is mutated.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// let mut health = Observable::new(100);
+    /// health.subscribe_coalesced(|updated_health| {
+    ///     println!("Health is now {updated_health}");
+    ///     Response::StaySubscribed
+    /// });
+    ///
+    /// health.mutate(|h| *h -= 10);
+    /// health.mutate(|h| *h -= 5);
+    /// health.pump(); // Prints "Health is now 85"
+    /// health.pump(); // Does not print anything
+    /// ```
+    pub fn subscribe_coalesced<C: FnMut(&T) -> Response + 'o + Send>(
+        &self,
+        callback: C,
+    ) -> Subscription {
+        self.observers.extra().coalesced.subscribe(callback)
+    }
+
+    /// Registers a new callback that will be called by [`Observable::sample`] with the value
+    /// contained in this observable. Mutations do not notify sampled callbacks.
+    ///
+    /// This is useful to observe a value at a fixed cadence (e.g. once per simulation step),
+    /// regardless of how often it changes.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// let mut position = Observable::new(0);
+    /// position.subscribe_sampled(|updated_position| {
+    ///     println!("Position is now {updated_position}");
+    ///     Response::StaySubscribed
+    /// });
+    ///
+    /// position.mutate(|p| *p += 1); // Does not print anything
+    /// position.mutate(|p| *p += 1); // Does not print anything
+    /// position.sample(); // Prints "Position is now 2"
+    /// position.sample(); // Prints "Position is now 2"
+    /// ```
+    pub fn subscribe_sampled<C: FnMut(&T) -> Response + 'o + Send>(
+        &self,
+        callback: C,
+    ) -> Subscription {
+        self.observers.extra().sampled.subscribe(callback)
+    }
+
+    /// Removes a callback that was previously registered.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// let mut health = Observable::new(100);
+    /// let subscription = health.subscribe(|updated_health| {
+    ///     println!("Health is now {updated_health}");
+    ///     Response::StaySubscribed
+    /// });
+    ///
+    /// health.unsubscribe(subscription);
+    /// ```
+    pub fn unsubscribe(&self, subscription: Subscription) {
+        self.observers.unsubscribe(subscription);
+    }
+
+    /// Combines this observable with another one, so that callbacks can observe both values together.
+    /// Existing subscriptions on either observable are preserved.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// let mut stats = Observable::new(100).zip(Observable::new(50));
+    /// stats.subscribe(|health, mana| {
+    ///     println!("Health is now {health}, mana is now {mana}");
+    ///     Response::StaySubscribed
+    /// });
+    /// stats.mutate_first(|h| *h -= 10); // Prints "Health is now 90, mana is now 50"
+    /// ```
+    pub fn zip<B>(self, other: Observable<'o, B>) -> ZippedObservable<'o, T, B> {
+        ZippedObservable::new(self, other)
+    }
+
+    /// Returns a reference to a delegate that will execute subscription functions
+    /// when the observable is mutated. This is useful when writing a struct that has
+    /// an observable member, but users of the struct should only have access to its
+    /// value by subscribing.
+    ///
+    /// ```rust
+    /// use squeak::{Delegate, Observable};
+    ///
+    /// struct MyStruct<'a> {
+    ///   observe_only: Observable<'a, u32>,
+    /// }
+    ///
+    /// impl<'a> MyStruct<'a> {
+    ///   pub fn delegate(&self) -> &Delegate<'a, u32> {
+    ///     self.observe_only.delegate()
+    ///   }
+    /// }
+    ///
+    /// ```
+    ///
+    pub fn delegate(&self) -> &Delegate<'o, T> {
+        &self.observers.delegate
+    }
+
+    /// Starts recording the values this observable takes after each mutation, along with the
+    /// time of the mutation. Only the `capacity` most recent changes are kept.
+    /// See [`Observable::change_log`].
+    ///
+    /// Calling this method again discards the changes recorded so far.
+    ///
+    /// This method requires the `std` feature.
+    ///
+    /// ```rust
+    /// use squeak::Observable;
+    ///
+    /// let mut health = Observable::new(100);
+    /// health.record_changes(10);
+    /// health.mutate(|h| *h -= 10);
+    /// assert_eq!(health.change_log()[0].value, 90);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn record_changes(&mut self, capacity: usize)
+    where
+        T: Clone + Send + 'o,
+    {
+        self.observers.change_log = Some(Box::new(BoundedChangeLog {
+            capacity,
+            records: Vec::with_capacity(capacity),
+        }));
+    }
+
+    /// Returns the changes recorded since calling [`Observable::record_changes`], from oldest
+    /// to newest. Returns an empty slice if changes are not being recorded.
+    ///
+    /// This method requires the `std` feature.
+    ///
+    /// ```rust
+    /// use squeak::Observable;
+    ///
+    /// let mut health = Observable::new(100);
+    /// health.record_changes(2);
+    /// health.mutate(|h| *h -= 10);
+    /// health.mutate(|h| *h -= 10);
+    /// health.mutate(|h| *h -= 10);
+    /// let log = health.change_log();
+    /// assert_eq!(log.len(), 2);
+    /// assert_eq!(log[1].value, 70);
+    /// assert!(log[0].at <= log[1].at);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn change_log(&self) -> &[ChangeRecord<T>] {
+        self.observers
+            .change_log
+            .as_ref()
+            .map_or(&[], |change_log| change_log.records())
+    }
+
+    /// Returns a rough estimate of the memory used by this observable, in bytes.
+    /// See [`Delegate::estimated_size`].
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// let health = Observable::new(100);
+    /// let empty_size = health.estimated_size();
+    /// health.subscribe(|_| Response::StaySubscribed);
+    /// assert!(health.estimated_size() > empty_size);
+    /// ```
+    pub fn estimated_size(&self) -> usize {
+        core::mem::size_of::<T>() + self.observers.estimated_size()
+    }
+
+    /// Returns a copy of the value contained in this observable, along with how many callbacks
+    /// are subscribed to it. This is intended for debugging tools.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// let health = Observable::new(100);
+    /// health.subscribe(|_| Response::StaySubscribed);
+    /// assert_eq!(health.debug_snapshot(), (100, 1));
+    /// ```
+    pub fn debug_snapshot(&self) -> (T, usize)
+    where
+        T: Clone,
+    {
+        (self.value.clone(), self.observers.subscriber_count())
+    }
+
+    /// Returns a read-only view of this observable, which can be used to subscribe to changes and
+    /// read the current value, but not to mutate it or broadcast to subscribers.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// let health = Observable::new(100);
+    /// let view = health.read_only();
+    /// view.subscribe(|updated_health| {
+    ///     println!("Health is now {updated_health}");
+    ///     Response::StaySubscribed
+    /// });
+    /// assert_eq!(*view.peek(), 100);
+    /// ```
+    pub fn read_only(&self) -> ReadOnlyObservable<'_, 'o, T> {
+        ReadOnlyObservable::new(self)
+    }
+
+    /// Returns a read-only view of a part of the value contained in this observable, selected by
+    /// `project`. Subscribers of the view receive a reference to that part, without cloning it.
+    /// See [`MappedObservable`] for details.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// let mut player = Observable::new((String::from("Lisa"), 100));
+    /// player.map_ref(|(name, _)| name).subscribe(|name| {
+    ///     println!("Name is now {name}");
+    ///     Response::StaySubscribed
+    /// });
+    /// player.mutate(|p| p.0.push('!')); // Prints "Name is now Lisa!"
+    /// ```
+    pub fn map_ref<V, F>(&self, project: F) -> MappedObservable<'_, 'o, T, V>
+    where
+        T: 'o,
+        V: ?Sized + 'o,
+        F: Fn(&T) -> &V + 'o + Send + Sync,
+    {
+        MappedObservable::new(self, project)
+    }
+
+    /// Creates a value derived from the value contained in this observable via `compute`.
+    /// The derived value is only computed when accessed, and cached until this observable
+    /// is mutated. See [`CachedValue`] for details.
+    ///
+    /// ```rust
+    /// use squeak::Observable;
+    ///
+    /// let mut inventory = Observable::new(vec![3, 5, 8]);
+    /// let mut total = inventory.cached_derive(|items| items.iter().sum::<u32>());
+    /// assert_eq!(*total.get(&inventory), 16);
+    /// inventory.mutate(|items| items.push(4));
+    /// assert_eq!(*total.get(&inventory), 20);
+    /// ```
+    pub fn cached_derive<'c, U, F: Fn(&T) -> U + 'c>(&self, compute: F) -> CachedValue<'c, T, U> {
+        CachedValue::new(compute)
+    }
+
+    /// Creates a buffer which records the last `capacity` values held by this observable,
+    /// for example to plot them. See [`ChartBuffer`] for details.
+    ///
+    /// This method requires the `std` feature.
+    ///
+    /// ```rust
+    /// use squeak::Observable;
+    ///
+    /// let mut fps = Observable::new(60);
+    /// let history = fps.chart_buffer(2);
+    /// fps.mutate(|f| *f = 58);
+    /// fps.mutate(|f| *f = 61);
+    /// fps.mutate(|f| *f = 59);
+    /// assert_eq!(history.to_vec(), vec![61, 59]);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn chart_buffer(&self, capacity: usize) -> ChartBuffer<T>
+    where
+        T: Clone + Send + 'o,
+    {
+        let buffer = ChartBuffer::new(capacity);
+        self.subscribe(buffer.recorder());
+        buffer
+    }
+
+    /// Keeps `target` in sync with the value contained in this observable. The current value is
+    /// copied into `target` immediately, then again after every mutation, until the returned
+    /// subscription is cancelled.
+    ///
+    /// Callbacks must be [`Send`], which rules out targets like `Rc<Cell<T>>` or `Rc<RefCell<T>>`:
+    /// the subscription would capture them and could then be moved to another thread. Mirroring
+    /// into a [`Mutex`] is the only variant, so this method requires the `std` feature.
+    ///
+    /// ```rust
+    /// use std::sync::{Arc, Mutex};
+    /// use squeak::Observable;
+    ///
+    /// let mut name = Observable::new(String::from("Lisa"));
+    /// let shadow = Arc::new(Mutex::new(String::new()));
+    /// name.mirror_into(shadow.clone());
+    /// assert_eq!(*shadow.lock().unwrap(), "Lisa");
+    /// name.mutate(|n| n.push('!'));
+    /// assert_eq!(*shadow.lock().unwrap(), "Lisa!");
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn mirror_into(&self, target: Arc<Mutex<T>>) -> Subscription
+    where
+        T: Clone + Send + 'o,
+    {
+        let mirror = move |value: &T| {
+            let mut target = target.lock().unwrap_or_else(PoisonError::into_inner);
+            target.clone_from(value);
+            Response::StaySubscribed
+        };
+        mirror(&self.value);
+        self.subscribe(mirror)
+    }
+
+    /// Execute a function which may mutate the value contained in this observable.
+    /// Subscription callbacks will be executed regardless of what happens inside
+    /// the `mutation` function.
+    ///
+    /// ```rust
+    /// use squeak::Observable;
+    ///
+    /// let mut name = Observable::new(String::from("DefaultName"));
+    /// name.mutate(|n| n.push_str("X"));
+    /// name.mutate(|n| n.push_str("Y"));
+    /// name.mutate(|n| n.push_str("Z"));
+    ///
+    /// assert_eq!(name.as_str(), "DefaultNameXYZ");
+    /// ```
+    pub fn mutate<M>(&mut self, mutation: M)
+    where
+        M: FnOnce(&mut T),
+    {
+        self.mutate_silently(mutation);
+        self.observers.notify(&self.value);
+    }
+
+    /// Execute a function which may mutate the value contained in this observable, like
+    /// [`Observable::mutate`]. Callbacks registered via [`Observable::subscribe_tagged_changes`]
+    /// for tags of type `Tag` receive `tag`, which lets them tell apart the causes of mutations.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// let mut volume = Observable::new(50);
+    /// volume.subscribe_tagged_changes(|source: &&str, v| {
+    ///     println!("Volume set to {v} by {source}");
+    ///     Response::StaySubscribed
+    /// });
+    /// volume.subscribe(|v| {
+    ///     println!("Volume set to {v}");
+    ///     Response::StaySubscribed
+    /// });
+    /// // Prints "Volume set to 80 by slider", then "Volume set to 80"
+    /// volume.mutate_tagged("slider", |v| *v = 80);
+    /// ```
+    pub fn mutate_tagged<Tag, M>(&mut self, tag: Tag, mutation: M)
+    where
+        Tag: 'static,
+        M: FnOnce(&mut T),
+    {
+        self.observers.begin_mutation(&self.value);
+        mutation(&mut self.value);
+        self.observers.end_mutation(&mut self.value);
+        self.observers.notify_with_context(&self.value, tag);
+    }
+
+    /// Returns a view of the part of the value contained in this observable selected by `lens`.
+    /// Mutations made through the view notify subscribers of this observable.
+    /// See [`FocusedObservable`] for details.
+    ///
+    /// ```rust
+    /// use squeak::{lens, Observable, Response};
+    ///
+    /// struct Settings {
+    ///     volume: u32,
+    /// }
+    ///
+    /// let mut settings = Observable::new(Settings { volume: 50 });
+    /// settings.subscribe(|s| {
+    ///     println!("Volume is now {}", s.volume);
+    ///     Response::StaySubscribed
+    /// });
+    /// settings.focus(lens!(Settings, volume)).mutate(|v| *v = 80); // Prints "Volume is now 80"
+    /// ```
+    pub fn focus<'a, V>(&'a mut self, lens: Lens<'a, T, V>) -> FocusedObservable<'a, 'o, T, V> {
+        FocusedObservable::new(self, lens)
+    }
+
+    /// Provides mutable access to the element at `index` of the value contained in this observable.
+    /// Subscription callbacks are executed when the returned guard is dropped. They receive the
+    /// entire value, not just the modified element.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// let mut scores = Observable::new(vec![10, 20, 30]);
+    /// scores.subscribe(|updated_scores| {
+    ///     println!("Scores are now {updated_scores:?}");
+    ///     Response::StaySubscribed
+    /// });
+    ///
+    /// *scores.get_index_mut(1) += 5; // Prints "Scores are now [10, 25, 30]"
+    /// ```
+    ///
+    /// Accessing the element through the guard panics if `index` is out of bounds. Maps from the
+    /// standard library do not implement [`IndexMut`], use [`Observable::mutate`] to edit them.
+    pub fn get_index_mut<I: Clone>(&mut self, index: I) -> IndexGuard<'_, 'o, T, I>
+    where
+        T: IndexMut<I>,
+    {
+        self.observers.begin_mutation(&self.value);
+        IndexGuard {
+            observable: self,
+            index,
+        }
+    }
+
+    /// Replaces the value contained in this observable and executes subscription callbacks with
+    /// the new value. The previous value is returned once all callbacks have completed.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// let mut weapon = Observable::new("sword");
+    /// weapon.subscribe(|updated_weapon| {
+    ///     println!("Now wielding {updated_weapon}");
+    ///     Response::StaySubscribed
+    /// });
+    ///
+    /// let previous = weapon.exchange("axe"); // Prints "Now wielding axe"
+    /// assert_eq!(previous, "sword");
+    /// ```
+    pub fn exchange(&mut self, new: T) -> T {
+        let mut old = new;
+        self.mutate(|value| core::mem::swap(value, &mut old));
+        old
+    }
+
+    /// Replaces the value contained in this observable with `new` and executes subscription callbacks,
+    /// but only if `predicate` returns true for the current value. Returns whether the value was replaced.
+    /// No callback is executed when the predicate returns false.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// let mut position = Observable::new((0, 0));
+    /// position.subscribe(|updated_position| {
+    ///     println!("Moved to {updated_position:?}");
+    ///     Response::StaySubscribed
+    /// });
+    ///
+    /// assert!(position.set_if(|p| *p == (0, 0), (3, 4))); // Prints "Moved to (3, 4)"
+    /// assert!(!position.set_if(|p| *p == (0, 0), (5, 5))); // Does not print anything
+    /// ```
+    pub fn set_if<P: FnOnce(&T) -> bool>(&mut self, predicate: P, new: T) -> bool {
+        if !predicate(&self.value) {
+            return false;
+        }
+        self.mutate(|value| *value = new);
+        true
+    }
+
+    /// Execute a fallible function which may mutate the value contained in this observable.
+    /// Subscription callbacks are only executed if the function returns `Ok`, otherwise the
+    /// error is returned to the caller.
+    ///
+    /// When the function fails, changes it made to the value before failing are not reverted.
+    /// Use [`Observable::snapshot`] and [`Observable::restore`] to roll them back if needed.
+    /// Hooks registered via [`Observable::add_post_hook`] are called either way.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// let mut level = Observable::new(1);
+    /// level.subscribe(|updated_level| {
+    ///     println!("Level is now {updated_level}");
+    ///     Response::StaySubscribed
+    /// });
+    ///
+    /// let input = "3";
+    /// level
+    ///     .try_mutate(|l| input.parse().map(|parsed| *l = parsed))
+    ///     .unwrap(); // Prints "Level is now 3"
+    ///
+    /// let input = "three";
+    /// assert!(level
+    ///     .try_mutate(|l| input.parse().map(|parsed| *l = parsed))
+    ///     .is_err()); // Does not print anything
+    /// ```
+    pub fn try_mutate<E, M>(&mut self, mutation: M) -> Result<(), E>
+    where
+        M: FnOnce(&mut T) -> Result<(), E>,
+    {
+        self.observers.begin_mutation(&self.value);
+        if let Err(error) = mutation(&mut self.value) {
+            self.observers.abort_mutation(&self.value);
+            return Err(error);
+        }
+        self.observers.end_mutation(&mut self.value);
+        self.observers.notify(&self.value);
+        Ok(())
+    }
+
+    pub(crate) fn mutate_silently<M: FnOnce(&mut T)>(&mut self, mutation: M) {
+        self.observers.begin_mutation(&self.value);
+        mutation(&mut self.value);
+        self.observers.end_mutation(&mut self.value);
+    }
+
+    /// Execute a function which may mutate the value contained in this observable, with
+    /// all-or-nothing semantics. If the function succeeds, subscription callbacks are executed
+    /// once and its result is returned. If it fails, the value is restored to what it was before
+    /// the call and subscription callbacks are not executed. Hooks registered via
+    /// [`Observable::add_post_hook`] are called either way, with the restored value on failure.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// let mut wallet = Observable::new((100, 0));
+    /// wallet.subscribe(|(gold, items)| {
+    ///     println!("{gold} gold and {items} items");
+    ///     Response::StaySubscribed
+    /// });
+    ///
+    /// let buy = |w: &mut (u32, u32), price: u32| -> Result<u32, &str> {
+    ///     w.1 += 1;
+    ///     w.0 = w.0.checked_sub(price).ok_or("not enough gold")?;
+    ///     Ok(w.1)
+    /// };
+    /// assert_eq!(wallet.transaction(|w| buy(w, 60)), Ok(1)); // Prints "40 gold and 1 items"
+    /// assert!(wallet.transaction(|w| buy(w, 60)).is_err()); // Does not print anything
+    /// assert_eq!(*wallet, (40, 1));
+    /// ```
+    pub fn transaction<R, E, F>(&mut self, transaction: F) -> Result<R, E>
+    where
+        T: Clone,
+        F: FnOnce(&mut T) -> Result<R, E>,
+    {
+        let snapshot = self.value.clone();
+        self.observers.begin_mutation(&self.value);
+        match transaction(&mut self.value) {
+            Ok(result) => {
+                self.observers.end_mutation(&mut self.value);
+                self.observers.notify(&self.value);
+                Ok(result)
+            }
+            Err(error) => {
+                self.value = snapshot;
+                self.observers.abort_mutation(&self.value);
+                Err(error)
+            }
+        }
+    }
+
+    /// Execute a function which may mutate the value contained in this observable, then returns
+    /// a reference to the updated value.
+    ///
+    /// ```rust
+    /// use squeak::Observable;
+    ///
+    /// let mut health = Observable::new(100);
+    /// if *health.update(|h| *h -= 100) == 0 {
+    ///     println!("Game over");
+    /// }
+    /// ```
+    pub fn update<M>(&mut self, mutation: M) -> &T
+    where
+        M: FnOnce(&mut T),
+    {
+        self.mutate(mutation);
+        &self.value
+    }
+
+    /// Execute a function which may mutate the value contained in this observable, then returns
+    /// whether this observable still has subscribers after executing their callbacks.
+    ///
+    /// This can be used to stop producing values once nobody is listening anymore.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// let mut health = Observable::new(100);
+    /// health.subscribe(|updated_health| {
+    ///     println!("Health is now {updated_health}");
+    ///     Response::CancelSubscription
+    /// });
+    ///
+    /// assert!(!health.mutate_and_check(|h| *h -= 10)); // Prints "Health is now 90"
+    /// ```
+    pub fn mutate_and_check<M>(&mut self, mutation: M) -> bool
+    where
+        M: FnOnce(&mut T),
+    {
+        self.mutate(mutation);
+        self.observers.subscriber_count() > 0
+    }
+
+    /// Execute a function which may mutate the value contained in this observable, then returns
+    /// how much the mutation changed the quantity computed by `measure`, as `after - before`.
+    ///
+    /// ```rust
+    /// use squeak::Observable;
+    ///
+    /// let mut health = Observable::with_validator(30_i32, |h| *h = (*h).max(0));
+    /// let change = health.mutate_delta(|h| *h, |h| *h -= 50);
+    /// assert_eq!(change, -30);
+    /// ```
+    pub fn mutate_delta<D, F, M>(&mut self, measure: F, mutation: M) -> D::Output
+    where
+        D: Sub,
+        F: Fn(&T) -> D,
+        M: FnOnce(&mut T),
+    {
+        let before = measure(&self.value);
+        self.mutate(mutation);
+        measure(&self.value) - before
+    }
+
+    /// Adds all items of `items` to the collection contained in this observable, then executes
+    /// subscription callbacks once.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// let mut inventory = Observable::new(Vec::new());
+    /// inventory.subscribe(|items: &Vec<&str>| {
+    ///     println!("Inventory now has {} items", items.len());
+    ///     Response::StaySubscribed
+    /// });
+    ///
+    /// inventory.extend_notify(["sword", "shield"]); // Prints "Inventory now has 2 items"
+    /// ```
+    pub fn extend_notify<I: IntoIterator>(&mut self, items: I)
+    where
+        T: Extend<I::Item>,
+    {
+        self.mutate(|value| value.extend(items));
+    }
+
+    /// Applies a mutation once per item in `items`, executing subscription callbacks
+    /// after each of them.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// let mut inventory = Observable::new(Vec::new());
+    /// inventory.subscribe(|items: &Vec<&str>| {
+    ///     println!("Inventory now has {} items", items.len());
+    ///     Response::StaySubscribed
+    /// });
+    ///
+    /// // Prints "Inventory now has 1 items", then "Inventory now has 2 items"
+    /// inventory.mutate_many(["sword", "shield"], |items, item| items.push(item));
+    /// ```
+    pub fn mutate_many<I, M>(&mut self, items: I, mut per_item: M)
+    where
+        I: IntoIterator,
+        M: FnMut(&mut T, I::Item),
+    {
+        for item in items {
+            self.mutate(|value| per_item(value, item));
+        }
+    }
+
+    /// Returns a copy of the value contained in this observable, which can later be passed to
+    /// [`Observable::restore`].
+    ///
+    /// Snapshots are made by cloning the value. For large collections, persistent data structures
+    /// like those of the [`im`](https://docs.rs/im) crate keep snapshots cheap: cloning them takes
+    /// constant time, and the snapshot shares its storage with the observed value.
+    ///
+    /// ```rust
+    /// use squeak::Observable;
+    ///
+    /// let mut name = Observable::new(String::from("DefaultName"));
+    /// let snapshot = name.snapshot();
+    /// name.mutate(|n| n.push_str("X"));
+    /// name.restore(snapshot);
+    /// assert_eq!(name.as_str(), "DefaultName");
+    /// ```
+    pub fn snapshot(&self) -> T
+    where
+        T: Clone,
+    {
+        self.value.clone()
+    }
+
+    /// Replaces the value contained in this observable with a snapshot previously created
+    /// via [`Observable::snapshot`]. Subscription callbacks are executed with the restored value.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// let mut health = Observable::new(100);
+    /// let snapshot = health.snapshot();
+    /// health.mutate(|h| *h -= 10);
+    /// health.subscribe(|updated_health| {
+    ///     println!("Health is now {updated_health}");
+    ///     Response::StaySubscribed
+    /// });
+    /// health.restore(snapshot); // Prints "Health is now 100"
+    /// ```
+    pub fn restore(&mut self, snapshot: T) {
+        self.mutate(|value| *value = snapshot);
+    }
+
+    /// Replaces the value contained in this observable without executing subscription callbacks.
+    ///
+    /// This is useful when restoring state, in combination with [`Observable::notify_current`]
+    /// to notify subscribers once all values are in place.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// let mut health = Observable::new(100);
+    /// health.subscribe(|updated_health| {
+    ///     println!("Health is now {updated_health}");
+    ///     Response::StaySubscribed
+    /// });
+    ///
+    /// health.set_silently(50); // Does not print anything
+    /// assert_eq!(*health, 50);
+    /// ```
+    pub fn set_silently(&mut self, value: T) {
+        self.observers.begin_mutation(&self.value);
+        self.value = value;
+        self.observers.end_mutation(&mut self.value);
+    }
+
+    /// Provides direct mutable access to the value contained in this observable, without
+    /// notifying subscribers. You must call [`Observable::notify_current`] yourself once your
+    /// edits are complete. Mutation hooks and validators do not run either.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// let mut position = Observable::new((0, 0));
+    /// position.subscribe(|new_position| {
+    ///     println!("Position is now {new_position:?}");
+    ///     Response::StaySubscribed
+    /// });
+    ///
+    /// let value = position.value_mut();
+    /// value.0 = 5;
+    /// value.1 = 10;
+    /// position.notify_current(); // Prints "Position is now (5, 10)"
+    /// ```
+    pub fn value_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+
+    /// Executes all subscription callbacks with the value currently contained in this observable.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// let mut health = Observable::new(100);
+    /// health.subscribe(|updated_health| {
+    ///     println!("Health is now {updated_health}");
+    ///     Response::StaySubscribed
+    /// });
+    ///
+    /// health.set_silently(50);
+    /// health.notify_current(); // Prints "Health is now 50"
+    /// ```
+    pub fn notify_current(&self) {
+        self.observers.notify(&self.value);
+    }
+
+    /// Executes callbacks registered via [`Observable::subscribe_coalesced`] with the value
+    /// currently contained in this observable, if it was mutated since the previous pump.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// let mut health = Observable::new(100);
+    /// health.subscribe_coalesced(|updated_health| {
+    ///     println!("Health is now {updated_health}");
+    ///     Response::StaySubscribed
+    /// });
+    ///
+    /// health.mutate(|h| *h -= 10);
+    /// health.pump(); // Prints "Health is now 90"
+    /// ```
+    pub fn pump(&self) {
+        self.observers.pump(&self.value);
+    }
+
+    /// Executes callbacks registered via [`Observable::subscribe_sampled`] with the value
+    /// currently contained in this observable, whether or not it was mutated since the previous sample.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// let position = Observable::new(0);
+    /// position.subscribe_sampled(|updated_position| {
+    ///     println!("Position is now {updated_position}");
+    ///     Response::StaySubscribed
+    /// });
+    ///
+    /// position.sample(); // Prints "Position is now 0"
+    /// ```
+    pub fn sample(&self) {
+        self.observers.sample(&self.value);
+    }
+}
+
+impl<'o, T> Observable<'o, Option<T>> {
+    /// Registers a new callback that will be called when a mutation changes the value contained
+    /// in this observable from `None` to `Some`. The callback receives the inner value.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// let mut target = Observable::new(None);
+    /// target.subscribe_on_some(|name| {
+    ///     println!("Now targeting {name}");
+    ///     Response::StaySubscribed
+    /// });
+    ///
+    /// target.mutate(|t| *t = Some("goblin")); // Prints "Now targeting goblin"
+    /// target.mutate(|t| *t = Some("orc")); // Does not print anything
+    /// target.mutate(|t| *t = None); // Does not print anything
+    /// target.mutate(|t| *t = Some("troll")); // Prints "Now targeting troll"
+    /// ```
+    pub fn subscribe_on_some<C: FnMut(&T) -> Response + 'o + Send>(
+        &self,
+        mut callback: C,
+    ) -> Subscription {
+        let mut was_some = self.value.is_some();
+        self.observers.delegate.subscribe(move |value| {
+            let became_some = !was_some;
+            was_some = value.is_some();
+            match value {
+                Some(inner) if became_some => callback(inner),
+                _ => Response::StaySubscribed,
+            }
+        })
+    }
+
+    /// Registers a new callback that will be called when a mutation changes the value contained
+    /// in this observable from `Some` to `None`.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// let mut target = Observable::new(Some("goblin"));
+    /// target.subscribe_on_none(|| {
+    ///     println!("Target lost");
+    ///     Response::StaySubscribed
+    /// });
+    ///
+    /// target.mutate(|t| *t = Some("orc")); // Does not print anything
+    /// target.mutate(|t| *t = None); // Prints "Target lost"
+    /// target.mutate(|t| *t = None); // Does not print anything
+    /// ```
+    pub fn subscribe_on_none<C: FnMut() -> Response + 'o + Send>(
+        &self,
+        mut callback: C,
+    ) -> Subscription {
+        let mut was_some = self.value.is_some();
+        self.observers.delegate.subscribe(move |value| {
+            let became_none = was_some && value.is_none();
+            was_some = value.is_some();
+            match became_none {
+                true => callback(),
+                false => Response::StaySubscribed,
+            }
+        })
+    }
+}
+
+impl<'o> Observable<'o, bool> {
+    /// Registers a new callback that will be called when a mutation changes the value contained
+    /// in this observable from `false` to `true`.
+    ///
+    /// The value held when subscribing is the starting point: subscribing to an observable which
+    /// already holds `true` does not count as a rising edge.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// let mut paused = Observable::new(false);
+    /// paused.subscribe_rising_edge(|| {
+    ///     println!("Showing pause menu");
+    ///     Response::StaySubscribed
+    /// });
+    ///
+    /// paused.mutate(|p| *p = true); // Prints "Showing pause menu"
+    /// paused.mutate(|p| *p = true); // Does not print anything
+    /// paused.mutate(|p| *p = false); // Does not print anything
+    /// ```
+    pub fn subscribe_rising_edge<C: FnMut() -> Response + 'o + Send>(
+        &self,
+        callback: C,
+    ) -> Subscription {
+        self.subscribe_edge(true, callback)
+    }
+
+    /// Registers a new callback that will be called when a mutation changes the value contained
+    /// in this observable from `true` to `false`.
+    ///
+    /// The value held when subscribing is the starting point: subscribing to an observable which
+    /// already holds `false` does not count as a falling edge.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// let mut paused = Observable::new(true);
+    /// paused.subscribe_falling_edge(|| {
+    ///     println!("Hiding pause menu");
+    ///     Response::StaySubscribed
+    /// });
+    ///
+    /// paused.mutate(|p| *p = false); // Prints "Hiding pause menu"
+    /// paused.mutate(|p| *p = false); // Does not print anything
+    /// ```
+    pub fn subscribe_falling_edge<C: FnMut() -> Response + 'o + Send>(
+        &self,
+        callback: C,
+    ) -> Subscription {
+        self.subscribe_edge(false, callback)
+    }
+
+    fn subscribe_edge<C: FnMut() -> Response + 'o + Send>(
+        &self,
+        target: bool,
+        mut callback: C,
+    ) -> Subscription {
+        let mut previous = self.value;
+        self.observers.delegate.subscribe(move |value| {
+            let crossed = previous != target && *value == target;
+            previous = *value;
+            match crossed {
+                true => callback(),
+                false => Response::StaySubscribed,
+            }
+        })
+    }
+}
+
+impl<'o, T> Observable<'o, T>
+where
+    T: SaturatingArithmetic + Copy,
+{
+    /// Adds `amount` to the value contained in this observable, clamping at the numeric bounds
+    /// instead of overflowing, then executes subscription callbacks.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// let mut gold = Observable::new(250_u8);
+    /// gold.subscribe(|updated_gold| {
+    ///     println!("Gold is now {updated_gold}");
+    ///     Response::StaySubscribed
+    /// });
+    /// gold.mutate_saturating_add(10); // Prints "Gold is now 255"
+    /// ```
+    pub fn mutate_saturating_add(&mut self, amount: T) {
+        self.mutate(|value| *value = value.saturating_add(amount));
+    }
+
+    /// Subtracts `amount` from the value contained in this observable, clamping at the numeric
+    /// bounds instead of overflowing, then executes subscription callbacks.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// let mut health = Observable::new(5_u32);
+    /// health.subscribe(|updated_health| {
+    ///     println!("Health is now {updated_health}");
+    ///     Response::StaySubscribed
+    /// });
+    /// health.mutate_saturating_sub(10); // Prints "Health is now 0"
+    /// ```
+    pub fn mutate_saturating_sub(&mut self, amount: T) {
+        self.mutate(|value| *value = value.saturating_sub(amount));
+    }
+}
+
+impl<'o, T> Observable<'o, Vec<T>>
+where
+    T: Clone + PartialEq + Send + 'o,
+{
+    /// Registers a new callback that will be called with the differences between the previous
+    /// and updated collections, whenever a mutation changes the collection contained in this
+    /// observable. Elements are compared by index, which takes O(n) time for each mutation.
+    ///
+    /// The subscription keeps a copy of the collection to compare it against the updated one.
+    /// Mutations which do not change the collection do not execute the callback.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// let mut inventory = Observable::new(vec!["sword"]);
+    /// inventory.subscribe_diff(|diff| {
+    ///     for (_, item) in &diff.added {
+    ///         println!("Picked up {item}");
+    ///     }
+    ///     Response::StaySubscribed
+    /// });
+    ///
+    /// inventory.mutate(|items| items.push("shield")); // Prints "Picked up shield"
+    /// ```
+    pub fn subscribe_diff<C: FnMut(&CollectionDiff<T>) -> Response + 'o + Send>(
+        &self,
+        callback: C,
+    ) -> Subscription {
+        self.subscribe_diff_with(CollectionDiff::by_index, callback)
+    }
+
+    /// Registers a new callback that will be called with the differences between the previous
+    /// and updated collections, whenever a mutation changes the collection contained in this
+    /// observable. Elements are identified by the output of `key`, so elements which moved within
+    /// the collection are not reported as changed. Keys should be unique within the collection.
+    /// This takes O(n log n) time for each mutation.
+    ///
+    /// The subscription keeps a copy of the collection to compare it against the updated one.
+    /// Mutations which do not change the collection do not execute the callback.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// let mut party = Observable::new(vec![("Lisa", 100), ("Bart", 80)]);
+    /// party.subscribe_diff_by_key(
+    ///     |(name, _)| *name,
+    ///     |diff| {
+    ///         for (_, (name, _)) in &diff.removed {
+    ///             println!("{name} left the party");
+    ///         }
+    ///         Response::StaySubscribed
+    ///     },
+    /// );
+    ///
+    /// party.mutate(|members| {
+    ///     members.remove(0); // Prints "Lisa left the party"
+    /// });
+    /// ```
+    pub fn subscribe_diff_by_key<K, F, C>(&self, key: F, callback: C) -> Subscription
+    where
+        K: Ord,
+        F: Fn(&T) -> K + 'o + Send,
+        C: FnMut(&CollectionDiff<T>) -> Response + 'o + Send,
+    {
+        self.subscribe_diff_with(
+            move |old, new| CollectionDiff::by_key(old, new, &key),
+            callback,
+        )
+    }
+
+    fn subscribe_diff_with<D, C>(&self, diff: D, mut callback: C) -> Subscription
+    where
+        D: Fn(&[T], &[T]) -> CollectionDiff<T> + 'o + Send,
+        C: FnMut(&CollectionDiff<T>) -> Response + 'o + Send,
+    {
+        let mut previous = self.value.clone();
+        self.observers.delegate.subscribe(move |value: &Vec<T>| {
+            let changes = diff(&previous, value);
+            previous.clone_from(value);
+            match changes.is_empty() {
+                true => Response::StaySubscribed,
+                false => callback(&changes),
+            }
+        })
+    }
+}
+
+/// Cloning an observable creates a new observable with a copy of the value.
+/// Subscriptions are not cloned: the new observable has no subscribers. Validators, hooks and
+/// change logs are not cloned either. The clone starts at the same [`Observable::version`], and is
+/// disarmed if this observable is (see [`Observable::new_disarmed`]).
+///
+/// ```rust
+/// use squeak::{Observable, Response};
+///
+/// let health = Observable::new(100);
+/// health.subscribe(|updated_health| {
+///     println!("Health is now {updated_health}");
+///     Response::StaySubscribed
+/// });
+/// let mut clone = health.clone();
+/// clone.mutate(|h| *h -= 10); // Does not print anything
+/// ```
+impl<T> Clone for Observable<'_, T>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        let mut clone = Self::new(self.value.clone());
+        clone.observers.armed = self.observers.armed;
+        clone.observers.version = self.observers.version;
+        clone
+    }
+}
+
+impl<T> Default for Observable<'_, T>
+where
+    T: Default,
 {
     fn default() -> Self {
         Self::new(Default::default())
     }
 }
 
+impl<T> From<T> for Observable<'_, T> {
+    /// Creates a new observable with an initial value.
+    ///
+    /// ```rust
+    /// use squeak::Observable;
+    /// let health: Observable<u32> = 100.into();
+    /// ```
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+/// Observables compare equal to values equal to their inner value.
+/// Subscriptions are not taken into account.
+///
+/// ```rust
+/// use squeak::Observable;
+/// let health = Observable::new(100);
+/// assert!(health == 100);
+/// ```
+impl<T> PartialEq<T> for Observable<'_, T>
+where
+    T: PartialEq,
+{
+    fn eq(&self, other: &T) -> bool {
+        self.value == *other
+    }
+}
+
+/// Observables compare equal when their inner values are equal.
+/// Subscriptions are not taken into account.
+///
+/// ```rust
+/// use squeak::Observable;
+/// assert!(Observable::new(100) == Observable::new(100));
+/// ```
+impl<'a, 'b, T> PartialEq<Observable<'b, T>> for Observable<'a, T>
+where
+    T: PartialEq,
+{
+    fn eq(&self, other: &Observable<'b, T>) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T> Eq for Observable<'_, T> where T: Eq {}
+
+/// Observables are ordered by their inner values.
+/// Subscriptions are not taken into account.
+///
+/// ```rust
+/// use squeak::Observable;
+/// assert!(Observable::new(10) < Observable::new(100));
+/// ```
+impl<'a, 'b, T> PartialOrd<Observable<'b, T>> for Observable<'a, T>
+where
+    T: PartialOrd,
+{
+    fn partial_cmp(&self, other: &Observable<'b, T>) -> Option<Ordering> {
+        self.value.partial_cmp(&other.value)
+    }
+}
+
+impl<T> Ord for Observable<'_, T>
+where
+    T: Ord,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.value.cmp(&other.value)
+    }
+}
+
+/// Observables hash to the same value as their inner value.
+/// Subscriptions are not taken into account.
+impl<T> Hash for Observable<'_, T>
+where
+    T: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+    }
+}
+
 impl<T> Deref for Observable<'_, T> {
     type Target = T;
     fn deref(&self) -> &Self::Target {
         &self.value
     }
 }
+
+/// Applies `+=` to the value contained in this observable, then executes subscription callbacks.
+///
+/// ```rust
+/// use squeak::{Observable, Response};
+///
+/// let mut health = Observable::new(100);
+/// health.subscribe(|updated_health| {
+///     println!("Health is now {updated_health}");
+///     Response::StaySubscribed
+/// });
+/// health += 10; // Prints "Health is now 110"
+/// ```
+impl<T> AddAssign<T> for Observable<'_, T>
+where
+    T: AddAssign,
+{
+    fn add_assign(&mut self, rhs: T) {
+        self.mutate(|value| *value += rhs);
+    }
+}
+
+/// Applies `-=` to the value contained in this observable, then executes subscription callbacks.
+///
+/// ```rust
+/// use squeak::{Observable, Response};
+///
+/// let mut health = Observable::new(100);
+/// health.subscribe(|updated_health| {
+///     println!("Health is now {updated_health}");
+///     Response::StaySubscribed
+/// });
+/// health -= 10; // Prints "Health is now 90"
+/// ```
+impl<T> SubAssign<T> for Observable<'_, T>
+where
+    T: SubAssign,
+{
+    fn sub_assign(&mut self, rhs: T) {
+        self.mutate(|value| *value -= rhs);
+    }
+}
+
+/// Applies `*=` to the value contained in this observable, then executes subscription callbacks.
+///
+/// ```rust
+/// use squeak::{Observable, Response};
+///
+/// let mut health = Observable::new(100);
+/// health.subscribe(|updated_health| {
+///     println!("Health is now {updated_health}");
+///     Response::StaySubscribed
+/// });
+/// health *= 2; // Prints "Health is now 200"
+/// ```
+impl<T> MulAssign<T> for Observable<'_, T>
+where
+    T: MulAssign,
+{
+    fn mul_assign(&mut self, rhs: T) {
+        self.mutate(|value| *value *= rhs);
+    }
+}
+
+/// Applies `/=` to the value contained in this observable, then executes subscription callbacks.
+///
+/// ```rust
+/// use squeak::{Observable, Response};
+///
+/// let mut health = Observable::new(100);
+/// health.subscribe(|updated_health| {
+///     println!("Health is now {updated_health}");
+///     Response::StaySubscribed
+/// });
+/// health /= 4; // Prints "Health is now 25"
+/// ```
+impl<T> DivAssign<T> for Observable<'_, T>
+where
+    T: DivAssign,
+{
+    fn div_assign(&mut self, rhs: T) {
+        self.mutate(|value| *value /= rhs);
+    }
+}
@@ -0,0 +1,129 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+use crate::{BoxedCallback, Observable, Response, Subscription};
+
+type Validator<'a, T> = Box<dyn Fn(&mut T) + 'a + Send>;
+
+/// Configures an [`Observable`] before creating it, created via [`Observable::builder`].
+///
+/// ```rust
+/// use squeak::{Observable, Response};
+///
+/// let (mut volume, subscriptions) = Observable::builder(150)
+///     .validator(|v: &mut u32| *v = (*v).min(100))
+///     .subscribe(|updated_volume| {
+///         println!("Volume is now {updated_volume}");
+///         Response::StaySubscribed
+///     })
+///     .replay()
+///     .build(); // Prints "Volume is now 100"
+///
+/// volume.mutate(|v| *v -= 10); // Prints "Volume is now 90"
+/// assert_eq!(subscriptions.len(), 1);
+/// ```
+pub struct ObservableBuilder<'o, T> {
+    value: T,
+    validator: Option<Validator<'o, T>>,
+    subscribers: Vec<BoxedCallback<'o, T>>,
+    replay: bool,
+}
+
+impl<'o, T> ObservableBuilder<'o, T> {
+    pub(crate) fn new(value: T) -> Self {
+        Self {
+            value,
+            validator: None,
+            subscribers: Vec::new(),
+            replay: false,
+        }
+    }
+}
+
+impl<'o, T: 'o> ObservableBuilder<'o, T> {
+    /// Sets the validator of the observable, like [`Observable::with_validator`].
+    ///
+    /// ```rust
+    /// use squeak::Observable;
+    ///
+    /// let (volume, _) = Observable::builder(150)
+    ///     .validator(|v: &mut u32| *v = (*v).min(100))
+    ///     .build();
+    /// assert_eq!(*volume, 100);
+    /// ```
+    pub fn validator<V: Fn(&mut T) + 'o + Send>(mut self, validator: V) -> Self {
+        self.validator = Some(Box::new(validator));
+        self
+    }
+
+    /// Registers a callback on the observable when it is built. The corresponding subscription
+    /// is returned by [`ObservableBuilder::build`].
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// let (mut health, _) = Observable::builder(100)
+    ///     .subscribe(|updated_health| {
+    ///         println!("Health is now {updated_health}");
+    ///         Response::StaySubscribed
+    ///     })
+    ///     .build();
+    /// health.mutate(|h| *h -= 10); // Prints "Health is now 90"
+    /// ```
+    pub fn subscribe<C: FnMut(&T) -> Response + 'o + Send>(mut self, callback: C) -> Self {
+        self.subscribers.push(Box::new(callback));
+        self
+    }
+
+    /// Executes the callbacks registered via [`ObservableBuilder::subscribe`] with the initial
+    /// value when the observable is built. Callbacks which respond with
+    /// [`Response::CancelSubscription`] are not registered.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// let (health, _) = Observable::builder(100)
+    ///     .subscribe(|updated_health| {
+    ///         println!("Health is now {updated_health}");
+    ///         Response::StaySubscribed
+    ///     })
+    ///     .replay()
+    ///     .build(); // Prints "Health is now 100"
+    /// ```
+    pub fn replay(mut self) -> Self {
+        self.replay = true;
+        self
+    }
+
+    /// Creates the observable, and returns it along with the subscriptions of the callbacks
+    /// registered via [`ObservableBuilder::subscribe`], in registration order.
+    ///
+    /// ```rust
+    /// use squeak::Observable;
+    ///
+    /// let (health, subscriptions) = Observable::builder(100).build();
+    /// assert!(subscriptions.is_empty());
+    /// ```
+    pub fn build(self) -> (Observable<'o, T>, Vec<Subscription>) {
+        let observable = match self.validator {
+            Some(validator) => Observable::with_validator(self.value, validator),
+            None => Observable::new(self.value),
+        };
+        let replay = self.replay;
+        let subscriptions = self
+            .subscribers
+            .into_iter()
+            .map(|mut callback| {
+                let response = match replay {
+                    true => callback(&observable),
+                    false => Response::StaySubscribed,
+                };
+                match response.is_cancellation() {
+                    true => Subscription::new(),
+                    false => observable.subscribe(callback),
+                }
+            })
+            .collect();
+        (observable, subscriptions)
+    }
+}
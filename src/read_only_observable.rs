@@ -0,0 +1,101 @@
+use alloc::fmt::Debug;
+
+use crate::{Observable, Response, Subscription};
+
+/// Read-only view of an [`Observable`], created via [`Observable::read_only`].
+///
+/// It can be used to subscribe to changes and read the current value, but not to mutate the
+/// value or broadcast to subscribers. This makes it suitable for handing observation rights
+/// to code which should not be able to trigger notifications.
+///
+/// ```rust
+/// use squeak::{Observable, ReadOnlyObservable, Response};
+///
+/// fn log_changes(health: ReadOnlyObservable<u32>) {
+///     println!("Health is {}", health.peek());
+///     health.subscribe(|updated_health| {
+///         println!("Health is now {updated_health}");
+///         Response::StaySubscribed
+///     });
+/// }
+///
+/// let health = Observable::new(100);
+/// log_changes(health.read_only()); // Prints "Health is 100"
+/// ```
+#[derive(Debug)]
+pub struct ReadOnlyObservable<'a, 'o, T> {
+    observable: &'a Observable<'o, T>,
+}
+
+impl<'a, 'o, T> ReadOnlyObservable<'a, 'o, T> {
+    pub(crate) fn new(observable: &'a Observable<'o, T>) -> Self {
+        Self { observable }
+    }
+
+    /// Registers a new callback that will be called when the value contained in the observable is mutated.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// let health = Observable::new(100);
+    /// health.read_only().subscribe(|updated_health| {
+    ///     println!("Health is now {updated_health}");
+    ///     Response::StaySubscribed
+    /// });
+    /// ```
+    pub fn subscribe<C: FnMut(&T) -> Response + 'o + Send>(&self, callback: C) -> Subscription {
+        self.observable.subscribe(callback)
+    }
+
+    /// Removes a callback that was previously registered.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// let health = Observable::new(100);
+    /// let view = health.read_only();
+    /// let subscription = view.subscribe(|updated_health| {
+    ///     println!("Health is now {updated_health}");
+    ///     Response::StaySubscribed
+    /// });
+    /// view.unsubscribe(subscription);
+    /// ```
+    pub fn unsubscribe(&self, subscription: Subscription) {
+        self.observable.unsubscribe(subscription);
+    }
+
+    /// Returns a reference to the value contained in the observable.
+    ///
+    /// ```rust
+    /// use squeak::Observable;
+    ///
+    /// let health = Observable::new(100);
+    /// assert_eq!(*health.read_only().peek(), 100);
+    /// ```
+    pub fn peek(&self) -> &'a T {
+        self.observable
+    }
+
+    /// Returns a copy of the value contained in the observable.
+    ///
+    /// ```rust
+    /// use squeak::Observable;
+    ///
+    /// let name = Observable::new(String::from("DefaultName"));
+    /// assert_eq!(name.read_only().get(), "DefaultName");
+    /// ```
+    pub fn get(&self) -> T
+    where
+        T: Clone,
+    {
+        self.peek().clone()
+    }
+}
+
+impl<T> Clone for ReadOnlyObservable<'_, '_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ReadOnlyObservable<'_, '_, T> {}
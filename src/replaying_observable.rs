@@ -0,0 +1,108 @@
+use alloc::fmt::Debug;
+use core::ops::Deref;
+
+use crate::{Delegate, Observable, Response, Subscription};
+
+/// Observable which immediately executes newly registered callbacks with its current value.
+///
+/// This makes it possible for subscribers to initialize themselves from the current value without
+/// waiting for the next mutation.
+///
+/// ```rust
+/// use squeak::{ReplayingObservable, Response};
+///
+/// let mut health = ReplayingObservable::new(100);
+/// health.subscribe(|updated_health| {
+///     println!("Health is now {updated_health}");
+///     Response::StaySubscribed
+/// }); // Prints "Health is now 100"
+///
+/// health.mutate(|h| *h -= 10); // Prints "Health is now 90"
+/// ```
+#[derive(Debug)]
+pub struct ReplayingObservable<'o, T> {
+    observable: Observable<'o, T>,
+}
+
+impl<'o, T> ReplayingObservable<'o, T> {
+    /// Creates a new replaying observable with an initial value
+    ///
+    /// ```rust
+    /// use squeak::ReplayingObservable;
+    /// let name = ReplayingObservable::new(String::from("DefaultName"));
+    /// ```
+    pub fn new(value: T) -> Self {
+        Self {
+            observable: Observable::new(value),
+        }
+    }
+
+    /// Registers a new callback and immediately executes it with the current value.
+    /// The callback will also be called every time the value contained in this observable is
+    /// mutated, unless its first execution returns [`Response::CancelSubscription`].
+    ///
+    /// ```rust
+    /// use squeak::{ReplayingObservable, Response};
+    ///
+    /// let health = ReplayingObservable::new(100);
+    /// health.subscribe(|updated_health| {
+    ///     println!("Health is now {updated_health}");
+    ///     Response::StaySubscribed
+    /// }); // Prints "Health is now 100"
+    /// ```
+    pub fn subscribe<F: FnMut(&T) -> Response + 'o + Send>(&self, mut callback: F) -> Subscription {
+        match callback(&self.observable) {
+            Response::StaySubscribed => self.observable.subscribe(callback),
+            Response::CancelSubscription => Subscription::new(),
+        }
+    }
+
+    /// Removes a callback that was previously registered.
+    ///
+    /// ```rust
+    /// use squeak::{ReplayingObservable, Response};
+    ///
+    /// let health = ReplayingObservable::new(100);
+    /// let subscription = health.subscribe(|updated_health| {
+    ///     println!("Health is now {updated_health}");
+    ///     Response::StaySubscribed
+    /// });
+    /// health.unsubscribe(subscription);
+    /// ```
+    pub fn unsubscribe(&self, subscription: Subscription) {
+        self.observable.unsubscribe(subscription);
+    }
+
+    /// Returns a reference to a delegate that will execute subscription functions
+    /// when the observable is mutated.
+    ///
+    /// Callbacks registered directly on this delegate are not executed with the current value.
+    pub fn delegate(&self) -> &Delegate<'o, T> {
+        self.observable.delegate()
+    }
+
+    /// Execute a function which may mutate the value contained in this observable.
+    /// Subscription callbacks will be executed regardless of what happens inside
+    /// the `mutation` function.
+    ///
+    /// ```rust
+    /// use squeak::ReplayingObservable;
+    ///
+    /// let mut name = ReplayingObservable::new(String::from("DefaultName"));
+    /// name.mutate(|n| n.push_str("X"));
+    /// assert_eq!(name.as_str(), "DefaultNameX");
+    /// ```
+    pub fn mutate<M>(&mut self, mutation: M)
+    where
+        M: FnOnce(&mut T),
+    {
+        self.observable.mutate(mutation);
+    }
+}
+
+impl<T> Deref for ReplayingObservable<'_, T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        &self.observable
+    }
+}
@@ -0,0 +1,158 @@
+use alloc::fmt::Debug;
+use core::cell::{Ref, RefCell};
+
+use crate::{Delegate, Response, Subscription};
+
+/// Observable whose value can be mutated through a shared reference.
+///
+/// Unlike [`Observable`](crate::Observable), [`SharedObservable::mutate`] only requires `&self`,
+/// which matches the rest of its API and avoids threading `&mut` access through an application.
+///
+/// ``` rust
+/// use squeak::{Response, SharedObservable};
+///
+/// let health = SharedObservable::new(100);
+/// health.subscribe(|updated_health| {
+///     println!("Health is now {updated_health}");
+///     Response::StaySubscribed
+/// });
+///
+/// health.mutate(|h| *h -= 10); // Prints "Health is now 90"
+/// health.mutate(|h| *h -= 5);  // Prints "Health is now 85"
+/// ```
+///
+/// The value is stored in a [`RefCell`]. Callbacks may read the value while it is being broadcast,
+/// but attempting to mutate it from within a callback panics.
+#[derive(Debug)]
+pub struct SharedObservable<'o, T> {
+    value: RefCell<T>,
+    delegate: Delegate<'o, T>,
+}
+
+impl<'o, T> SharedObservable<'o, T> {
+    /// Creates a new shared observable with an initial value
+    ///
+    /// ```rust
+    /// use squeak::SharedObservable;
+    /// let name = SharedObservable::new(String::from("DefaultName"));
+    /// ```
+    pub fn new(value: T) -> Self {
+        Self {
+            value: RefCell::new(value),
+            delegate: Delegate::new(),
+        }
+    }
+
+    /// Registers a new callback that will be called when the value contained in this observable is mutated.
+    ///
+    /// ```rust
+    /// use squeak::{Response, SharedObservable};
+    ///
+    /// let health = SharedObservable::new(100);
+    /// health.subscribe(|updated_health| {
+    ///     println!("Health is now {updated_health}");
+    ///     Response::StaySubscribed
+    /// });
+    /// ```
+    pub fn subscribe<C: FnMut(&T) -> Response + 'o + Send>(&self, callback: C) -> Subscription {
+        self.delegate.subscribe(callback)
+    }
+
+    /// Removes a callback that was previously registered.
+    ///
+    /// ```rust
+    /// use squeak::{Response, SharedObservable};
+    ///
+    /// let health = SharedObservable::new(100);
+    /// let subscription = health.subscribe(|updated_health| {
+    ///     println!("Health is now {updated_health}");
+    ///     Response::StaySubscribed
+    /// });
+    /// health.unsubscribe(subscription);
+    /// ```
+    pub fn unsubscribe(&self, subscription: Subscription) {
+        self.delegate.unsubscribe(subscription);
+    }
+
+    /// Returns a reference to a delegate that will execute subscription functions
+    /// when the observable is mutated.
+    pub fn delegate(&self) -> &Delegate<'o, T> {
+        &self.delegate
+    }
+
+    /// Immutably borrows the value contained in this observable.
+    ///
+    /// ```rust
+    /// use squeak::SharedObservable;
+    ///
+    /// let name = SharedObservable::new(String::from("DefaultName"));
+    /// assert_eq!(name.borrow().as_str(), "DefaultName");
+    /// ```
+    pub fn borrow(&self) -> Ref<'_, T> {
+        self.value.borrow()
+    }
+
+    /// Returns a copy of the value contained in this observable.
+    ///
+    /// ```rust
+    /// use squeak::SharedObservable;
+    ///
+    /// let health = SharedObservable::new(100);
+    /// assert_eq!(health.get(), 100);
+    /// ```
+    pub fn get(&self) -> T
+    where
+        T: Clone,
+    {
+        self.value.borrow().clone()
+    }
+
+    /// Returns a copy of the value contained in this observable, along with how many callbacks
+    /// are subscribed to it. Both are read while the value is borrowed, so they cannot be
+    /// separated by a mutation. This is intended for debugging tools.
+    ///
+    /// ```rust
+    /// use squeak::{Response, SharedObservable};
+    ///
+    /// let health = SharedObservable::new(100);
+    /// health.subscribe(|_| Response::StaySubscribed);
+    /// assert_eq!(health.debug_snapshot(), (100, 1));
+    /// ```
+    pub fn debug_snapshot(&self) -> (T, usize)
+    where
+        T: Clone,
+    {
+        let value = self.value.borrow();
+        (value.clone(), self.delegate.subscriber_count())
+    }
+
+    /// Execute a function which may mutate the value contained in this observable.
+    /// Subscription callbacks will be executed regardless of what happens inside
+    /// the `mutation` function.
+    ///
+    /// ```rust
+    /// use squeak::SharedObservable;
+    ///
+    /// let name = SharedObservable::new(String::from("DefaultName"));
+    /// name.mutate(|n| n.push_str("X"));
+    /// name.mutate(|n| n.push_str("Y"));
+    ///
+    /// assert_eq!(name.borrow().as_str(), "DefaultNameXY");
+    /// ```
+    pub fn mutate<M>(&self, mutation: M)
+    where
+        M: FnOnce(&mut T),
+    {
+        mutation(&mut self.value.borrow_mut());
+        self.delegate.broadcast_borrowed(&self.value.borrow());
+    }
+}
+
+impl<T> Default for SharedObservable<'_, T>
+where
+    T: Default,
+{
+    fn default() -> Self {
+        Self::new(Default::default())
+    }
+}
@@ -0,0 +1,120 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+use crate::Observable;
+
+trait PendingMutation {
+    fn apply(&mut self);
+    fn notify(&self);
+}
+
+struct Pending<'t, 'o, T, M> {
+    observable: &'t mut Observable<'o, T>,
+    mutation: Option<M>,
+}
+
+impl<T, M: FnOnce(&mut T)> PendingMutation for Pending<'_, '_, T, M> {
+    fn apply(&mut self) {
+        if let Some(mutation) = self.mutation.take() {
+            self.observable.mutate_silently(mutation);
+        }
+    }
+
+    fn notify(&self) {
+        self.observable.notify_current();
+    }
+}
+
+/// Group of mutations to several observables, which are applied together.
+///
+/// Mutations registered via [`Transaction::mutate`] are not applied right away. When the
+/// transaction is committed, all mutations are applied first, then the subscribers of each
+/// observable are notified, in the order the mutations were registered. This way, subscribers
+/// never observe a state where only some of the mutations were applied. Dropping a transaction
+/// without committing it discards its mutations.
+///
+/// ```rust
+/// use squeak::{Observable, Response, Transaction};
+///
+/// let mut gold = Observable::new(100);
+/// let mut items = Observable::new(Vec::<&str>::new());
+/// gold.subscribe(|g| {
+///     println!("Gold is now {g}");
+///     Response::StaySubscribed
+/// });
+///
+/// let mut transaction = Transaction::new();
+/// transaction.mutate(&mut gold, |g| *g -= 60);
+/// transaction.mutate(&mut items, |i| i.push("sword"));
+/// transaction.commit(); // Prints "Gold is now 40"
+/// ```
+///
+/// Atomicity is only guaranteed with respect to subscribers. Transactions do not lock anything,
+/// and exclusive access to the observables is enforced by the borrow checker.
+#[derive(Default)]
+pub struct Transaction<'t> {
+    pending: Vec<Box<dyn PendingMutation + 't>>,
+}
+
+impl<'t> Transaction<'t> {
+    /// Creates an empty transaction.
+    ///
+    /// ```rust
+    /// use squeak::Transaction;
+    ///
+    /// let transaction = Transaction::new();
+    /// transaction.commit(); // Does nothing
+    /// ```
+    pub fn new() -> Self {
+        Self {
+            pending: Vec::new(),
+        }
+    }
+
+    /// Registers a mutation of `observable`, to be applied when this transaction is committed.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Transaction};
+    ///
+    /// let mut health = Observable::new(100);
+    /// {
+    ///     let mut transaction = Transaction::new();
+    ///     transaction.mutate(&mut health, |h| *h -= 10);
+    /// } // Transaction is dropped without being committed
+    /// assert_eq!(*health, 100);
+    /// ```
+    pub fn mutate<'o, T, M>(&mut self, observable: &'t mut Observable<'o, T>, mutation: M)
+    where
+        T: 't,
+        M: FnOnce(&mut T) + 't,
+        'o: 't,
+    {
+        self.pending.push(Box::new(Pending {
+            observable,
+            mutation: Some(mutation),
+        }));
+    }
+
+    /// Applies all registered mutations, then executes the subscription callbacks of each
+    /// mutated observable.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Transaction};
+    ///
+    /// let mut x = Observable::new(0);
+    /// let mut y = Observable::new(0);
+    /// let mut transaction = Transaction::new();
+    /// transaction.mutate(&mut x, |x| *x = 5);
+    /// transaction.mutate(&mut y, |y| *y = 8);
+    /// transaction.commit();
+    /// assert_eq!((*x, *y), (5, 8));
+    /// ```
+    pub fn commit(mut self) {
+        for pending in &mut self.pending {
+            pending.apply();
+        }
+        for pending in &self.pending {
+            pending.notify();
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use alloc::rc::{Rc, Weak};
+
+use crate::{Delegate, Response, Subscription};
+
+/// Non-owning reference to a delegate shared via [`Rc`], created via [`Delegate::downgrade`].
+///
+/// A weak delegate does not keep its delegate alive. This makes it possible for an object
+/// to hold on to the delegate of its owner without creating a reference cycle.
+///
+/// ```rust
+/// use std::rc::Rc;
+/// use squeak::{Delegate, Response};
+///
+/// let on_damage_received = Rc::new(Delegate::new());
+/// let weak = Delegate::downgrade(&on_damage_received);
+/// weak.subscribe(|amount: &u32| {
+///     println!("Received {amount} damage");
+///     Response::StaySubscribed
+/// });
+/// on_damage_received.broadcast(5); // Prints "Received 5 damage"
+///
+/// drop(on_damage_received);
+/// assert!(weak.upgrade().is_none());
+/// ```
+///
+/// Like [`OwnedSubscription`](crate::OwnedSubscription), weak delegates rely on [`Rc`] and are
+/// therefore not [`Send`].
+pub struct WeakDelegate<'d, T: ?Sized> {
+    delegate: Weak<Delegate<'d, T>>,
+}
+
+impl<'d, T: ?Sized> WeakDelegate<'d, T> {
+    /// Returns the delegate, if it is still alive.
+    ///
+    /// ```rust
+    /// use std::rc::Rc;
+    /// use squeak::Delegate;
+    ///
+    /// let on_damage_received = Rc::new(Delegate::<u32>::new());
+    /// let weak = Delegate::downgrade(&on_damage_received);
+    /// assert!(weak.upgrade().is_some());
+    /// ```
+    pub fn upgrade(&self) -> Option<Rc<Delegate<'d, T>>> {
+        self.delegate.upgrade()
+    }
+
+    /// Registers a new callback on the delegate, if it is still alive.
+    /// Returns `None` if the delegate was dropped.
+    ///
+    /// ```rust
+    /// use std::rc::Rc;
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_damage_received = Rc::new(Delegate::new());
+    /// let weak = Delegate::downgrade(&on_damage_received);
+    /// let subscription = weak.subscribe(|amount: &u32| {
+    ///     println!("Received {amount} damage");
+    ///     Response::StaySubscribed
+    /// });
+    /// assert!(subscription.is_some());
+    /// ```
+    pub fn subscribe<C: FnMut(&T) -> Response + 'd + Send>(
+        &self,
+        callback: C,
+    ) -> Option<Subscription> {
+        self.upgrade().map(|delegate| delegate.subscribe(callback))
+    }
+
+    /// Removes a callback that was previously registered on the delegate, if it is still alive.
+    ///
+    /// ```rust
+    /// use std::rc::Rc;
+    /// use squeak::{Delegate, Response};
+    ///
+    /// let on_damage_received = Rc::new(Delegate::new());
+    /// let weak = Delegate::downgrade(&on_damage_received);
+    /// let subscription = weak.subscribe(|amount: &u32| {
+    ///     println!("Received {amount} damage");
+    ///     Response::StaySubscribed
+    /// });
+    /// weak.unsubscribe(subscription.unwrap());
+    /// on_damage_received.broadcast(5); // Does not print anything
+    /// ```
+    pub fn unsubscribe(&self, subscription: Subscription) {
+        if let Some(delegate) = self.upgrade() {
+            delegate.unsubscribe(subscription);
+        }
+    }
+}
+
+impl<T: ?Sized> Clone for WeakDelegate<'_, T> {
+    fn clone(&self) -> Self {
+        Self {
+            delegate: self.delegate.clone(),
+        }
+    }
+}
+
+impl<'d, T: ?Sized> Delegate<'d, T> {
+    /// Creates a [`WeakDelegate`] referring to `delegate`.
+    ///
+    /// ```rust
+    /// use std::rc::Rc;
+    /// use squeak::Delegate;
+    ///
+    /// let on_damage_received = Rc::new(Delegate::<u32>::new());
+    /// let weak = Delegate::downgrade(&on_damage_received);
+    /// ```
+    pub fn downgrade(delegate: &Rc<Self>) -> WeakDelegate<'d, T> {
+        WeakDelegate {
+            delegate: Rc::downgrade(delegate),
+        }
+    }
+}
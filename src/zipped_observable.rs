@@ -0,0 +1,186 @@
+use alloc::fmt::Debug;
+
+use crate::observable::Observers;
+use crate::{Delegate, Observable, Response, Subscription};
+
+/// Pair of observables which can be observed together, created via [`Observable::zip`].
+///
+/// Callbacks registered via [`ZippedObservable::subscribe`] are executed with the current value
+/// of both observables whenever either of them is mutated. Callbacks registered on the original
+/// observables before they were zipped keep being executed when their own value is mutated, but
+/// not when the other value is.
+///
+/// ```rust
+/// use squeak::{Observable, Response};
+///
+/// let health = Observable::new(100);
+/// let mana = Observable::new(50);
+/// let mut stats = health.zip(mana);
+/// stats.subscribe(|health, mana| {
+///     println!("Health is now {health}, mana is now {mana}");
+///     Response::StaySubscribed
+/// });
+///
+/// stats.mutate_first(|h| *h -= 10); // Prints "Health is now 90, mana is now 50"
+/// stats.mutate_second(|m| *m -= 5); // Prints "Health is now 90, mana is now 45"
+/// ```
+#[derive(Debug)]
+pub struct ZippedObservable<'z, A, B> {
+    values: (A, B),
+    first_observers: Observers<'z, A>,
+    second_observers: Observers<'z, B>,
+    delegate: Delegate<'z, (A, B)>,
+}
+
+impl<'z, A, B> ZippedObservable<'z, A, B> {
+    pub(crate) fn new(first: Observable<'z, A>, second: Observable<'z, B>) -> Self {
+        let (first_value, first_observers) = first.into_parts();
+        let (second_value, second_observers) = second.into_parts();
+        Self {
+            values: (first_value, second_value),
+            first_observers,
+            second_observers,
+            delegate: Delegate::new(),
+        }
+    }
+
+    /// Returns the value of the first observable.
+    ///
+    /// ```rust
+    /// use squeak::Observable;
+    ///
+    /// let stats = Observable::new(100).zip(Observable::new(50));
+    /// assert_eq!(*stats.first(), 100);
+    /// ```
+    pub fn first(&self) -> &A {
+        &self.values.0
+    }
+
+    /// Returns the value of the second observable.
+    ///
+    /// ```rust
+    /// use squeak::Observable;
+    ///
+    /// let stats = Observable::new(100).zip(Observable::new(50));
+    /// assert_eq!(*stats.second(), 50);
+    /// ```
+    pub fn second(&self) -> &B {
+        &self.values.1
+    }
+
+    /// Registers a new callback that will be called with both values when either of them is mutated.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// let stats = Observable::new(100).zip(Observable::new(50));
+    /// stats.subscribe(|health, mana| {
+    ///     println!("Health is now {health}, mana is now {mana}");
+    ///     Response::StaySubscribed
+    /// });
+    /// ```
+    pub fn subscribe<C: FnMut(&A, &B) -> Response + 'z + Send>(
+        &self,
+        mut callback: C,
+    ) -> Subscription {
+        self.delegate.subscribe(move |(a, b)| callback(a, b))
+    }
+
+    /// Removes a callback that was previously registered.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// let stats = Observable::new(100).zip(Observable::new(50));
+    /// let subscription = stats.subscribe(|health, mana| {
+    ///     println!("Health is now {health}, mana is now {mana}");
+    ///     Response::StaySubscribed
+    /// });
+    /// stats.unsubscribe(subscription);
+    /// ```
+    pub fn unsubscribe(&self, subscription: Subscription) {
+        self.delegate.unsubscribe(subscription);
+    }
+
+    /// Execute a function which may mutate the value of the first observable.
+    /// Callbacks registered on the first observable and on this zipped observable will be executed.
+    ///
+    /// ```rust
+    /// use squeak::Observable;
+    ///
+    /// let mut stats = Observable::new(100).zip(Observable::new(50));
+    /// stats.mutate_first(|h| *h -= 10);
+    /// assert_eq!(*stats.first(), 90);
+    /// ```
+    pub fn mutate_first<M>(&mut self, mutation: M)
+    where
+        M: FnOnce(&mut A),
+    {
+        self.first_observers.begin_mutation(&self.values.0);
+        mutation(&mut self.values.0);
+        self.first_observers.end_mutation(&mut self.values.0);
+        self.first_observers.notify(&self.values.0);
+        self.delegate.broadcast_borrowed(&self.values);
+    }
+
+    /// Execute a function which may mutate the value of the second observable.
+    /// Callbacks registered on the second observable and on this zipped observable will be executed.
+    ///
+    /// ```rust
+    /// use squeak::Observable;
+    ///
+    /// let mut stats = Observable::new(100).zip(Observable::new(50));
+    /// stats.mutate_second(|m| *m -= 5);
+    /// assert_eq!(*stats.second(), 45);
+    /// ```
+    pub fn mutate_second<M>(&mut self, mutation: M)
+    where
+        M: FnOnce(&mut B),
+    {
+        self.second_observers.begin_mutation(&self.values.1);
+        mutation(&mut self.values.1);
+        self.second_observers.end_mutation(&mut self.values.1);
+        self.second_observers.notify(&self.values.1);
+        self.delegate.broadcast_borrowed(&self.values);
+    }
+
+    /// Executes coalesced callbacks registered on either of the original observables,
+    /// like [`Observable::pump`] would.
+    ///
+    /// ```rust
+    /// use squeak::{Observable, Response};
+    ///
+    /// let health = Observable::new(100);
+    /// health.subscribe_coalesced(|updated_health| {
+    ///     println!("Health is now {updated_health}");
+    ///     Response::StaySubscribed
+    /// });
+    /// let mut stats = health.zip(Observable::new(50));
+    /// stats.mutate_first(|h| *h -= 10);
+    /// stats.pump(); // Prints "Health is now 90"
+    /// ```
+    pub fn pump(&self) {
+        self.first_observers.pump(&self.values.0);
+        self.second_observers.pump(&self.values.1);
+    }
+
+    /// Splits this zipped observable back into the two original observables.
+    /// Callbacks registered on the zipped observable are dropped.
+    ///
+    /// ```rust
+    /// use squeak::Observable;
+    ///
+    /// let mut stats = Observable::new(100).zip(Observable::new(50));
+    /// stats.mutate_first(|h| *h -= 10);
+    /// let (health, mana) = stats.unzip();
+    /// assert_eq!(*health, 90);
+    /// assert_eq!(*mana, 50);
+    /// ```
+    pub fn unzip(self) -> (Observable<'z, A>, Observable<'z, B>) {
+        let (first_value, second_value) = self.values;
+        (
+            Observable::from_parts(first_value, self.first_observers),
+            Observable::from_parts(second_value, self.second_observers),
+        )
+    }
+}
@@ -0,0 +1,27 @@
+use squeak::{BorrowedObservable, Response};
+
+struct Character {
+    name: String,
+    health: u32,
+}
+
+#[test]
+fn borrowed_observable_mutates_and_broadcasts_field() {
+    let mut character = Character {
+        name: String::from("Lisa"),
+        health: 100,
+    };
+    let mut seen_value = 0;
+    {
+        let mut health = BorrowedObservable::new(&mut character.health);
+        health.subscribe(|new_value| {
+            seen_value = *new_value;
+            Response::StaySubscribed
+        });
+        health.mutate(|value| *value -= 10);
+        assert_eq!(*health, 90);
+        assert_eq!(character.name, "Lisa");
+    }
+    assert_eq!(seen_value, 90);
+    assert_eq!(character.health, 90);
+}
@@ -0,0 +1,22 @@
+use std::cell::Cell;
+
+use squeak::Observable;
+
+#[test]
+fn cached_value_recomputes_only_after_mutations() {
+    let compute_count = Cell::new(0);
+    let mut o = Observable::new(vec![1, 2, 3]);
+    let mut total = o.cached_derive(|values| {
+        compute_count.set(compute_count.get() + 1);
+        values.iter().sum::<i32>()
+    });
+    assert_eq!(compute_count.get(), 0);
+    assert_eq!(*total.get(&o), 6);
+    assert_eq!(*total.get(&o), 6);
+    assert_eq!(compute_count.get(), 1);
+    o.mutate(|values| values.push(4));
+    o.set_silently(vec![10]);
+    assert!(total.is_stale(&o));
+    assert_eq!(*total.get(&o), 10);
+    assert_eq!(compute_count.get(), 2);
+}
@@ -0,0 +1,29 @@
+use squeak::{CancellationToken, Delegate, Observable, Response};
+
+#[test]
+fn cancelling_token_removes_subscriptions_across_delegates() {
+    let mut damage_calls = 0;
+    let mut health_calls = 0;
+    {
+        let token = CancellationToken::new();
+        let on_damage_received = Delegate::new();
+        let mut health = Observable::new(100);
+        on_damage_received.subscribe_until(&token, |_: &u32| {
+            damage_calls += 1;
+            Response::StaySubscribed
+        });
+        health.subscribe_until(&token.clone(), |_| {
+            health_calls += 1;
+            Response::StaySubscribed
+        });
+        on_damage_received.broadcast(5);
+        health.mutate(|h| *h -= 5);
+        token.cancel();
+        on_damage_received.broadcast(5);
+        health.mutate(|h| *h -= 5);
+        assert_eq!(on_damage_received.subscriber_count(), 0);
+        assert_eq!(health.delegate().subscriber_count(), 0);
+    }
+    assert_eq!(damage_calls, 1);
+    assert_eq!(health_calls, 1);
+}
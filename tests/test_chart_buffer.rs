@@ -0,0 +1,25 @@
+#![cfg(feature = "std")]
+
+use squeak::Observable;
+
+#[test]
+fn chart_buffer_keeps_most_recent_values() {
+    let mut o = Observable::new(0);
+    let history = o.chart_buffer(3);
+    assert!(history.is_empty());
+    for i in 1..=5 {
+        o.mutate(|v| *v = i);
+    }
+    assert_eq!(history.len(), 3);
+    assert_eq!(history.to_vec(), vec![3, 4, 5]);
+    assert_eq!(history.with_slice(|values| values.iter().sum::<i32>()), 12);
+}
+
+#[test]
+fn chart_buffer_unsubscribes_after_drop() {
+    let mut o = Observable::new(0);
+    let history = o.chart_buffer(3);
+    drop(history);
+    o.mutate(|v| *v = 1);
+    assert_eq!(o.delegate().subscriber_count(), 0);
+}
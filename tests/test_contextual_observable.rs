@@ -0,0 +1,21 @@
+use parking_lot::Mutex;
+
+use squeak::{Observable, Response};
+
+#[test]
+fn shared_callback_receives_context_of_each_observable() {
+    let seen = Mutex::new(Vec::new());
+    {
+        let record = |name: &&'static str, value: &u32| {
+            seen.lock().push((*name, *value));
+            Response::StaySubscribed
+        };
+        let mut health = Observable::with_context(100, "health");
+        let mut mana = Observable::with_context(50, "mana");
+        health.subscribe_with_context(record);
+        mana.subscribe_with_context(record);
+        health.mutate(|h| *h -= 10);
+        mana.mutate(|m| *m -= 5);
+    }
+    assert_eq!(*seen.lock(), vec![("health", 90), ("mana", 45)]);
+}
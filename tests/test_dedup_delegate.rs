@@ -0,0 +1,20 @@
+use squeak::{DedupDelegate, Response};
+
+#[test]
+fn consecutive_duplicates_are_not_broadcast() {
+    let mut seen_values = Vec::new();
+    {
+        let d = DedupDelegate::new();
+        d.subscribe(|value: &u32| {
+            seen_values.push(*value);
+            Response::StaySubscribed
+        });
+        d.broadcast(1);
+        d.broadcast(1);
+        d.broadcast(2);
+        d.broadcast(1);
+        d.reset();
+        d.broadcast(1);
+    }
+    assert_eq!(seen_values, vec![1, 2, 1, 1]);
+}
@@ -1,7 +1,16 @@
 use parking_lot::{Mutex, ReentrantMutex};
-use std::{cell::RefCell, ops::Deref, sync::Arc};
+use std::{
+    borrow::Cow,
+    cell::RefCell,
+    ops::Deref,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
+};
 
-use squeak::{Delegate, Response};
+use squeak::{BoxedCallback, BroadcastError, Delegate, Handled, Response, SubscriptionMeta};
 
 #[test]
 fn delegate_executes_callbacks() {
@@ -77,7 +86,7 @@ fn cannot_unsubscribe_using_subscription_from_a_different_delegate() {
 }
 
 #[test]
-fn unsubscribing_within_callback_is_noop() {
+fn unsubscribing_within_callback_takes_effect_immediately() {
     let d = Arc::new(ReentrantMutex::new(Delegate::new()));
     let call_count = Arc::new(Mutex::new(RefCell::new(0)));
     let subscription = Arc::new(Mutex::new(RefCell::new(None)));
@@ -99,7 +108,33 @@ fn unsubscribing_within_callback_is_noop() {
 
     d.lock().notify();
     d.lock().notify();
-    assert_eq!(*call_count.lock().borrow(), 2);
+    assert_eq!(*call_count.lock().borrow(), 1);
+    assert_eq!(d.lock().subscriber_count(), 0);
+}
+
+#[test]
+fn callbacks_unsubscribed_mid_broadcast_are_skipped() {
+    let d = Arc::new(ReentrantMutex::new(Delegate::new()));
+    let call_count = Arc::new(Mutex::new(0));
+    let victim = Arc::new(Mutex::new(None));
+
+    let d_clone = d.clone();
+    let victim_clone = victim.clone();
+    d.lock().subscribe(move |_| {
+        if let Some(subscription) = victim_clone.lock().take() {
+            d_clone.lock().unsubscribe(subscription);
+        }
+        Response::StaySubscribed
+    });
+    let call_count_clone = call_count.clone();
+    *victim.lock() = Some(d.lock().subscribe(move |_| {
+        *call_count_clone.lock() += 1;
+        Response::StaySubscribed
+    }));
+
+    d.lock().notify();
+    assert_eq!(*call_count.lock(), 0);
+    assert_eq!(d.lock().subscriber_count(), 1);
 }
 
 #[test]
@@ -116,3 +151,1465 @@ fn can_unsubscribe_using_response_value() {
     }
     assert_eq!(call_count, 1);
 }
+
+#[test]
+fn can_collect_callbacks_into_delegate() {
+    let call_count = Arc::new(Mutex::new(0));
+    let make_callback = |stay_subscribed: bool| -> BoxedCallback<()> {
+        let call_count = call_count.clone();
+        Box::new(move |_| {
+            *call_count.lock() += 1;
+            match stay_subscribed {
+                true => Response::StaySubscribed,
+                false => Response::CancelSubscription,
+            }
+        })
+    };
+    let mut d: Delegate<()> = [make_callback(true), make_callback(false)]
+        .into_iter()
+        .collect();
+    d.extend([make_callback(true)]);
+    d.notify();
+    d.notify();
+    assert_eq!(*call_count.lock(), 5);
+}
+
+#[test]
+fn can_create_delegate_with_subscribers() {
+    let call_count = Arc::new(Mutex::new(0));
+    let make_callback = || -> BoxedCallback<()> {
+        let call_count = call_count.clone();
+        Box::new(move |_| {
+            *call_count.lock() += 1;
+            Response::StaySubscribed
+        })
+    };
+    let (d, mut subscriptions) =
+        Delegate::new_with_subscribers([make_callback(), make_callback(), make_callback()]);
+    assert_eq!(subscriptions.len(), 3);
+    assert_eq!(d.subscriber_count(), 3);
+    d.unsubscribe(subscriptions.remove(1));
+    d.notify();
+    assert_eq!(*call_count.lock(), 2);
+}
+
+#[test]
+fn broadcast_iter_executes_one_callback_per_step() {
+    let call_count = Arc::new(AtomicU64::new(0));
+    let d = Delegate::new();
+    let make_callback = |response: fn() -> Response| {
+        let call_count = call_count.clone();
+        move |_: &u32| {
+            call_count.fetch_add(1, Ordering::SeqCst);
+            response()
+        }
+    };
+    let a = d.subscribe(make_callback(|| Response::CancelSubscription));
+    let b = d.subscribe(make_callback(|| Response::StaySubscribed));
+    d.subscribe(make_callback(|| Response::StaySubscribed));
+
+    let mut iter = d.broadcast_iter(&0);
+    assert_eq!(call_count.load(Ordering::SeqCst), 0);
+    let (id, response) = iter.next().unwrap();
+    assert_eq!(id, a.id());
+    assert!(response.is_cancellation());
+    assert!(!d.is_subscribed(&a));
+    let (id, response) = iter.next().unwrap();
+    assert_eq!(id, b.id());
+    assert!(!response.is_cancellation());
+    drop(iter);
+
+    assert_eq!(call_count.load(Ordering::SeqCst), 2);
+    assert_eq!(d.subscriber_count(), 2);
+    assert_eq!(d.broadcast_depth(), 0);
+    assert_eq!(d.broadcast_iter(&0).count(), 2);
+}
+
+#[test]
+fn nested_broadcasts_increase_depth() {
+    let d = Arc::new(ReentrantMutex::new(Delegate::new()));
+    let seen_depths = Arc::new(Mutex::new(Vec::new()));
+    {
+        let d_clone = d.clone();
+        d.lock().subscribe(move |nest: &bool| {
+            if *nest {
+                d_clone.lock().broadcast(false);
+            }
+            Response::StaySubscribed
+        });
+        let d_clone = d.clone();
+        let seen_depths_clone = seen_depths.clone();
+        d.lock().subscribe(move |_| {
+            seen_depths_clone
+                .lock()
+                .push(d_clone.lock().broadcast_depth());
+            Response::StaySubscribed
+        });
+        d.lock().broadcast(true);
+    }
+    assert_eq!(*seen_depths.lock(), vec![2, 1]);
+    assert_eq!(d.lock().broadcast_depth(), 0);
+    assert_eq!(d.lock().reentrant_broadcast_count(), 1);
+}
+
+#[test]
+fn nested_broadcasts_beyond_max_depth_are_skipped() {
+    let d = Arc::new(ReentrantMutex::new(Delegate::new()));
+    let call_count = Arc::new(Mutex::new(0));
+    let nested_results = Arc::new(Mutex::new(Vec::new()));
+    {
+        d.lock().set_max_depth(Some(1));
+        let d_clone = d.clone();
+        let nested_results_clone = nested_results.clone();
+        d.lock().subscribe(move |_| {
+            let result = d_clone.lock().try_broadcast(());
+            nested_results_clone.lock().push(result);
+            Response::StaySubscribed
+        });
+        let call_count_clone = call_count.clone();
+        d.lock().subscribe(move |_| {
+            *call_count_clone.lock() += 1;
+            Response::StaySubscribed
+        });
+        assert_eq!(d.lock().try_broadcast(()), Ok(()));
+    }
+    assert_eq!(*call_count.lock(), 1);
+    assert_eq!(
+        *nested_results.lock(),
+        vec![Err(BroadcastError::MaxDepthExceeded)]
+    );
+    assert_eq!(d.lock().reentrant_broadcast_count(), 1);
+}
+
+#[test]
+fn recursive_broadcasts_of_same_value_can_be_rejected() {
+    let d = Arc::new(ReentrantMutex::new(Delegate::new()));
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let echo_results = Arc::new(Mutex::new(Vec::new()));
+    {
+        d.lock().reject_recursive_same_value();
+        let d_clone = d.clone();
+        let echo_results_clone = echo_results.clone();
+        d.lock().subscribe(move |value: &u32| {
+            echo_results_clone
+                .lock()
+                .push(d_clone.lock().try_broadcast(*value));
+            if *value == 0 {
+                d_clone.lock().broadcast(1);
+            }
+            Response::StaySubscribed
+        });
+        let received_clone = received.clone();
+        d.lock().subscribe(move |value: &u32| {
+            received_clone.lock().push(*value);
+            Response::StaySubscribed
+        });
+        assert_eq!(d.lock().try_broadcast(0), Ok(()));
+    }
+    assert_eq!(*received.lock(), vec![1, 0]);
+    assert_eq!(
+        *echo_results.lock(),
+        vec![Err(BroadcastError::RecursiveSameValue)]
+    );
+}
+
+#[test]
+fn reducing_subscriptions_emit_on_flush() {
+    let totals = Arc::new(Mutex::new(Vec::new()));
+    {
+        let d = Delegate::new();
+        let totals_clone = totals.clone();
+        d.subscribe_reducing(
+            0,
+            |total, amount: &u32| total + amount,
+            move |total| totals_clone.lock().push(total),
+        );
+        d.broadcast(5);
+        d.broadcast(10);
+        d.flush();
+        d.flush();
+        d.broadcast(1);
+        d.flush();
+    }
+    assert_eq!(*totals.lock(), vec![15, 1]);
+}
+
+#[test]
+fn can_share_callback_between_delegates() {
+    let call_count = Arc::new(Mutex::new(0));
+    let call_count_clone = call_count.clone();
+    let callback = move |_: &()| {
+        *call_count_clone.lock() += 1;
+        Response::StaySubscribed
+    };
+    {
+        let d1 = Delegate::new();
+        let d2 = Delegate::new();
+        d1.subscribe_shared(&callback);
+        d2.subscribe_shared(&callback);
+        d1.notify();
+        d2.notify();
+    }
+    assert_eq!(*call_count.lock(), 2);
+}
+
+#[test]
+fn broadcast_reports_cancelled_subscriptions() {
+    let d = Delegate::new();
+    let _s1 = d.subscribe(|_| Response::StaySubscribed);
+    let s2 = d.subscribe(|_| Response::CancelSubscription);
+    let cancelled = d.broadcast_reporting(());
+    assert!(cancelled == vec![s2]);
+    assert!(d.broadcast_reporting(()).is_empty());
+}
+
+#[test]
+fn invocation_hook_sees_each_invoked_subscription() {
+    let invoked = Arc::new(Mutex::new(Vec::new()));
+    let d = Delegate::new();
+    let s1 = d.subscribe(|_| Response::StaySubscribed);
+    let s2 = d.subscribe(|_| Response::CancelSubscription);
+    let invoked_clone = invoked.clone();
+    d.set_invocation_hook(move |id| invoked_clone.lock().push(id));
+    d.notify();
+    d.notify();
+    d.clear_invocation_hook();
+    d.notify();
+    assert_eq!(*invoked.lock(), vec![s1.id(), s2.id(), s1.id()]);
+}
+
+#[test]
+fn deferred_unsubscribe_within_callback_takes_effect_after_broadcast() {
+    let d = Arc::new(ReentrantMutex::new(Delegate::new()));
+    let call_count = Arc::new(Mutex::new(0));
+    let subscription = Arc::new(Mutex::new(None));
+
+    let d_clone = d.clone();
+    let call_count_clone = call_count.clone();
+    let subscription_clone = subscription.clone();
+    *subscription.lock() = Some(d.lock().subscribe(move |_| {
+        *call_count_clone.lock() += 1;
+        if let Some(subscription) = subscription_clone.lock().take() {
+            d_clone.lock().unsubscribe_deferred(subscription);
+        }
+        Response::StaySubscribed
+    }));
+
+    d.lock().notify();
+    d.lock().notify();
+    assert_eq!(*call_count.lock(), 1);
+}
+
+#[test]
+fn can_defer_unsubscribing_other_callbacks() {
+    let d = Arc::new(ReentrantMutex::new(Delegate::new()));
+    let call_count = Arc::new(Mutex::new(0));
+
+    let call_count_clone = call_count.clone();
+    let victim = Arc::new(Mutex::new(Some(d.lock().subscribe(move |_| {
+        *call_count_clone.lock() += 1;
+        Response::StaySubscribed
+    }))));
+
+    let d_clone = d.clone();
+    d.lock().subscribe(move |_| {
+        if let Some(subscription) = victim.lock().take() {
+            d_clone.lock().unsubscribe_deferred(subscription);
+        }
+        Response::StaySubscribed
+    });
+
+    d.lock().notify();
+    d.lock().notify();
+    assert_eq!(*call_count.lock(), 1);
+}
+
+#[test]
+fn deferred_unsubscribe_outside_broadcast_is_immediate() {
+    let mut call_count = 0;
+    {
+        let d = Delegate::new();
+        let subscription = d.subscribe(|_| {
+            call_count += 1;
+            Response::StaySubscribed
+        });
+        d.unsubscribe_deferred(subscription);
+        d.notify();
+    }
+    assert_eq!(call_count, 0);
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn async_callbacks_are_spawned_on_broadcast() {
+    let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+    let d = Delegate::new();
+    d.subscribe_async(move |value: u32| {
+        let sender = sender.clone();
+        async move {
+            sender.send(value * 2).unwrap();
+        }
+    });
+    d.broadcast(1);
+    d.broadcast(2);
+    let mut received = vec![
+        receiver.recv().await.unwrap(),
+        receiver.recv().await.unwrap(),
+    ];
+    received.sort();
+    assert_eq!(received, vec![2, 4]);
+}
+
+#[test]
+fn controlled_subscription_can_cancel_itself() {
+    let mut seen_values = Vec::new();
+    {
+        let d = Delegate::new();
+        d.subscribe_controlled(|value, control| {
+            seen_values.push(*value);
+            if *value == 2 {
+                control.cancel();
+            }
+        });
+        for value in 1..=3 {
+            d.broadcast(value);
+        }
+    }
+    assert_eq!(seen_values, vec![1, 2]);
+}
+
+#[test]
+fn fallback_only_runs_when_no_other_callback_ran() {
+    let fallback_calls = Arc::new(Mutex::new(0));
+    let regular_calls = Arc::new(Mutex::new(0));
+    let d = Delegate::new();
+
+    let fallback_calls_clone = fallback_calls.clone();
+    d.subscribe_fallback(move |_| {
+        *fallback_calls_clone.lock() += 1;
+        Response::StaySubscribed
+    });
+    d.notify();
+    assert_eq!(*fallback_calls.lock(), 1);
+
+    let regular_calls_clone = regular_calls.clone();
+    d.subscribe(move |_| {
+        *regular_calls_clone.lock() += 1;
+        Response::CancelSubscription
+    });
+    d.notify();
+    d.notify();
+    assert_eq!(*regular_calls.lock(), 1);
+    assert_eq!(*fallback_calls.lock(), 2);
+}
+
+#[test]
+fn can_count_active_subscriptions() {
+    let d = Delegate::new();
+    let s1 = d.subscribe(|_| Response::StaySubscribed);
+    let s2 = d.subscribe(|_| Response::CancelSubscription);
+    let s3 = d.subscribe(|_| Response::StaySubscribed);
+    assert_eq!(d.subscriber_count(), 3);
+    assert_eq!(d.count_if(|s| *s == s1 || *s == s2), 2);
+    d.notify();
+    assert_eq!(d.subscriber_count(), 2);
+    assert_eq!(d.count_if(|s| *s == s1 || *s == s2), 1);
+    d.unsubscribe(s3);
+    assert_eq!(d.subscriber_count(), 1);
+}
+
+#[test]
+fn outer_broadcast_skips_subscriptions_cancelled_by_nested_broadcast() {
+    let d = Arc::new(ReentrantMutex::new(Delegate::new()));
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    {
+        let d_clone = d.clone();
+        let seen_clone = seen.clone();
+        d.lock().subscribe(move |nest: &bool| {
+            seen_clone.lock().push(("nesting", *nest));
+            if *nest {
+                d_clone.lock().broadcast(false);
+            }
+            Response::StaySubscribed
+        });
+        let seen_clone = seen.clone();
+        d.lock().subscribe(move |nest: &bool| {
+            seen_clone.lock().push(("cancelling", *nest));
+            Response::CancelSubscription
+        });
+        d.lock().broadcast(true);
+    }
+    assert_eq!(*seen.lock(), vec![("nesting", true), ("cancelling", false)]);
+    assert_eq!(d.lock().subscriber_count(), 1);
+}
+
+#[test]
+fn outer_broadcast_skips_subscriptions_added_by_nested_broadcast() {
+    let d = Arc::new(ReentrantMutex::new(Delegate::new()));
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    {
+        let d_clone = d.clone();
+        let seen_clone = seen.clone();
+        d.lock().subscribe(move |nest: &bool| {
+            if *nest {
+                let seen_clone = seen_clone.clone();
+                d_clone.lock().subscribe(move |nest: &bool| {
+                    seen_clone.lock().push(*nest);
+                    Response::StaySubscribed
+                });
+                d_clone.lock().broadcast(false);
+            }
+            Response::CancelSubscription
+        });
+        d.lock().broadcast(true);
+        d.lock().broadcast(true);
+    }
+    assert_eq!(*seen.lock(), vec![false, true]);
+}
+
+#[test]
+fn sequence_numbers_reveal_missed_broadcasts() {
+    let d = Delegate::new();
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    d.broadcast(10);
+    let seen_clone = seen.clone();
+    d.subscribe_seq(move |seq, value: &u32| {
+        seen_clone.lock().push((seq, *value));
+        match *value {
+            30 => Response::CancelSubscription,
+            _ => Response::StaySubscribed,
+        }
+    });
+    d.broadcast(20);
+    d.broadcast(30);
+    d.broadcast(40);
+    assert_eq!(*seen.lock(), vec![(2, 20), (3, 30)]);
+    assert_eq!(d.current_seq(), 4);
+}
+
+#[test]
+fn nested_broadcast_does_not_alter_outer_sequence_number() {
+    let d = Arc::new(ReentrantMutex::new(Delegate::new()));
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    {
+        let d_clone = d.clone();
+        d.lock().subscribe(move |nest: &bool| {
+            if *nest {
+                d_clone.lock().broadcast(false);
+            }
+            Response::StaySubscribed
+        });
+        let seen_clone = seen.clone();
+        d.lock().subscribe_seq(move |seq, nest: &bool| {
+            seen_clone.lock().push((seq, *nest));
+            Response::StaySubscribed
+        });
+        d.lock().broadcast(true);
+    }
+    assert_eq!(*seen.lock(), vec![(2, false), (1, true)]);
+}
+
+#[test]
+fn inspect_sees_values_until_unsubscribed() {
+    let d = Delegate::new();
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let seen_clone = seen.clone();
+    let tap = d.inspect(move |value: &u32| seen_clone.lock().push(*value));
+    d.broadcast(1);
+    d.broadcast(2);
+    d.unsubscribe(tap);
+    d.broadcast(3);
+    assert_eq!(*seen.lock(), vec![1, 2]);
+}
+
+#[test]
+fn broadcast_into_converts_value() {
+    let d = Delegate::new();
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let seen_clone = seen.clone();
+    d.inspect(move |value: &String| seen_clone.lock().push(value.clone()));
+    d.broadcast_into("Lisa");
+    d.broadcast_into('J');
+    assert_eq!(*seen.lock(), vec![String::from("Lisa"), String::from("J")]);
+}
+
+#[test]
+fn subscribe_on_posts_callbacks_instead_of_running_them() {
+    let d = Delegate::new();
+    let queue = Arc::new(Mutex::new(Vec::<Box<dyn FnOnce() + Send>>::new()));
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let queue_clone = queue.clone();
+    let seen_clone = seen.clone();
+    d.subscribe_on(
+        move |task| queue_clone.lock().push(task),
+        move |value: u32| seen_clone.lock().push(value),
+    );
+    d.broadcast(1);
+    d.broadcast(2);
+    assert!(seen.lock().is_empty());
+    for task in queue.lock().drain(..) {
+        task();
+    }
+    assert_eq!(*seen.lock(), vec![1, 2]);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn profiled_broadcast_reports_each_executed_callback() {
+    let d = Delegate::new();
+    let a = d.subscribe(|_: &u32| {
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        Response::StaySubscribed
+    });
+    let b = d.subscribe(|_| Response::CancelSubscription);
+    d.subscribe_fallback(|_| Response::StaySubscribed);
+    let profile = d.broadcast_profiled(1);
+    let ids: Vec<u64> = profile.subscriptions.iter().map(|(id, _)| *id).collect();
+    assert_eq!(ids, vec![a.id(), b.id()]);
+    assert!(profile.subscriptions[0].1 >= std::time::Duration::from_millis(5));
+    assert!(profile.total >= profile.subscriptions[0].1);
+    assert_eq!(d.subscriber_count(), 2);
+}
+
+#[test]
+fn owned_subscription_unsubscribes_on_drop() {
+    let d = Rc::new(Delegate::new());
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let seen_clone = seen.clone();
+    let subscription = Delegate::subscribe_owning(&d, move |value: &u32| {
+        seen_clone.lock().push(*value);
+        Response::StaySubscribed
+    });
+    d.broadcast(1);
+    drop(subscription);
+    d.broadcast(2);
+    assert_eq!(*seen.lock(), vec![1]);
+    assert_eq!(d.subscriber_count(), 0);
+
+    let subscription = Delegate::subscribe_owning(&d, |_| Response::StaySubscribed);
+    drop(d);
+    drop(subscription);
+}
+
+#[test]
+fn dispatch_stops_at_first_consuming_handler() {
+    let d = Delegate::new();
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let seen_clone = seen.clone();
+    d.subscribe(move |value: &u32| {
+        seen_clone.lock().push(("regular", *value));
+        Response::StaySubscribed
+    });
+    let seen_clone = seen.clone();
+    d.subscribe_handler(move |value| {
+        seen_clone.lock().push(("even", *value));
+        match value % 2 {
+            0 => Handled::Consumed,
+            _ => Handled::Passed,
+        }
+    });
+    let seen_clone = seen.clone();
+    d.subscribe_handler(move |value| {
+        seen_clone.lock().push(("any", *value));
+        Handled::Consumed
+    });
+    assert!(d.dispatch(2));
+    assert!(d.dispatch(3));
+    d.broadcast(4);
+    assert_eq!(
+        *seen.lock(),
+        vec![
+            ("regular", 2),
+            ("even", 2),
+            ("regular", 3),
+            ("even", 3),
+            ("any", 3),
+            ("regular", 4),
+            ("even", 4),
+            ("any", 4),
+        ]
+    );
+}
+
+#[test]
+fn simulate_reports_responses_without_unsubscribing() {
+    let d = Delegate::new();
+    let call_count = Arc::new(AtomicU64::new(0));
+    let call_count_clone = call_count.clone();
+    let a = d.subscribe(move |_: &u32| {
+        call_count_clone.fetch_add(1, Ordering::SeqCst);
+        Response::StaySubscribed
+    });
+    let a_id = a.id();
+    let b = d.subscribe_with_handle(move |_: &u32, handle| {
+        handle.unsubscribe(a_id);
+        Response::CancelSubscription
+    });
+    for _ in 0..2 {
+        let responses = d.simulate(0);
+        assert_eq!(responses.len(), 2);
+        assert_eq!(responses[0].0, a.id());
+        assert!(!responses[0].1.is_cancellation());
+        assert_eq!(responses[1].0, b.id());
+        assert!(responses[1].1.is_cancellation());
+    }
+    assert_eq!(call_count.load(Ordering::SeqCst), 2);
+    assert!(d.is_subscribed(&a));
+    assert!(d.is_subscribed(&b));
+}
+
+#[test]
+fn simulate_leaves_delegate_state_untouched() {
+    let d = Delegate::new();
+    d.remember_last_broadcast();
+    d.broadcast(1);
+    let counted = d.subscribe_counted(|_, _: &u32| Response::CancelSubscription);
+    let reduced = Arc::new(Mutex::new(Vec::new()));
+    let reduced_clone = reduced.clone();
+    d.subscribe_reducing(
+        0,
+        |total, value: &u32| total + value,
+        move |total| reduced_clone.lock().push(total),
+    );
+    let responses = d.simulate(5);
+    assert_eq!(responses.len(), 1);
+    assert_eq!(responses[0].0, counted.id());
+    assert_eq!(d.current_seq(), 1);
+    assert_eq!(d.last_broadcast(), Some(1));
+    assert_eq!(d.invocation_count_of(&counted), 0);
+    d.flush();
+    assert!(reduced.lock().is_empty());
+}
+
+#[test]
+fn simulate_does_not_call_invocation_hook() {
+    let invoked = Arc::new(Mutex::new(Vec::new()));
+    let d = Delegate::new();
+    let subscription = d.subscribe(|_: &u32| Response::StaySubscribed);
+    let invoked_clone = invoked.clone();
+    d.set_invocation_hook(move |id| invoked_clone.lock().push(id));
+    assert_eq!(d.simulate(5).len(), 1);
+    assert!(invoked.lock().is_empty());
+    d.broadcast(5);
+    assert_eq!(*invoked.lock(), vec![subscription.id()]);
+}
+
+#[test]
+fn group_broadcasts_only_reach_their_group() {
+    let d = Arc::new(ReentrantMutex::new(Delegate::new()));
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let seen_clone = seen.clone();
+    let d_clone = d.clone();
+    d.lock().subscribe_in_group("critical", move |value: &u32| {
+        seen_clone.lock().push(("critical", *value));
+        if *value == 1 {
+            d_clone.lock().broadcast_to_group("background", 2);
+        }
+        Response::StaySubscribed
+    });
+    let seen_clone = seen.clone();
+    d.lock()
+        .subscribe_in_group("background", move |value: &u32| {
+            seen_clone.lock().push(("background", *value));
+            Response::StaySubscribed
+        });
+    let seen_clone = seen.clone();
+    d.lock().subscribe(move |value: &u32| {
+        seen_clone.lock().push(("ungrouped", *value));
+        Response::StaySubscribed
+    });
+    d.lock().broadcast_to_group("critical", 1);
+    d.lock().broadcast(3);
+    assert_eq!(
+        *seen.lock(),
+        vec![
+            ("critical", 1),
+            ("background", 2),
+            ("critical", 3),
+            ("background", 3),
+            ("ungrouped", 3),
+        ]
+    );
+}
+
+#[test]
+fn clear_returning_metadata_removes_everything() {
+    let d = Delegate::<u32>::new();
+    let a = d.subscribe_in_group("ui", |_| Response::StaySubscribed);
+    let b = d.subscribe_fallback(|_| Response::StaySubscribed);
+    d.mute(&b);
+    let metadata = d.clear_returning_metadata();
+    assert_eq!(d.subscriber_count(), 0);
+    assert_eq!(
+        metadata,
+        vec![
+            SubscriptionMeta {
+                id: a.id(),
+                priority: 0,
+                group: Some(String::from("ui")),
+                fallback: false,
+                muted: false,
+            },
+            SubscriptionMeta {
+                id: b.id(),
+                priority: 0,
+                group: None,
+                fallback: true,
+                muted: true,
+            },
+        ]
+    );
+}
+
+#[test]
+fn estimated_size_grows_with_subscriptions() {
+    let d = Delegate::<u32>::new();
+    let empty = d.estimated_size();
+    let a = d.subscribe(|_| Response::StaySubscribed);
+    let one = d.estimated_size();
+    d.subscribe(|_| Response::StaySubscribed);
+    let two = d.estimated_size();
+    assert!(one > empty);
+    assert_eq!(two - one, one - empty);
+    d.unsubscribe(a);
+    assert_eq!(d.estimated_size(), one);
+}
+
+#[test]
+fn subscribe_with_lets_callbacks_unsubscribe_themselves() {
+    let d = Arc::new(ReentrantMutex::new(Delegate::new()));
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let seen_clone = seen.clone();
+    let d_clone = d.clone();
+    let subscription = d.lock().subscribe_with(|subscription| {
+        let mut subscription = Some(subscription);
+        move |value: &u32| {
+            let id = subscription.as_ref().map(|s| s.id());
+            seen_clone.lock().push((id, *value));
+            if *value == 2 {
+                if let Some(subscription) = subscription.take() {
+                    d_clone.lock().unsubscribe(subscription);
+                }
+            }
+            Response::StaySubscribed
+        }
+    });
+    let id = Some(subscription.id());
+    for value in 1..=3 {
+        d.lock().broadcast(value);
+    }
+    assert_eq!(*seen.lock(), vec![(id, 1), (id, 2)]);
+    assert!(!d.lock().is_subscribed(&subscription));
+}
+
+#[test]
+fn gated_subscriptions_follow_shared_gate() {
+    let d = Delegate::new();
+    let gate = Arc::new(AtomicBool::new(false));
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    for name in ['a', 'b'] {
+        let seen = seen.clone();
+        d.subscribe_gated(&gate, move |value: &u32| {
+            seen.lock().push((name, *value));
+            Response::StaySubscribed
+        });
+    }
+    d.broadcast(1);
+    gate.store(true, Ordering::SeqCst);
+    d.broadcast(2);
+    gate.store(false, Ordering::SeqCst);
+    d.broadcast(3);
+    assert_eq!(*seen.lock(), vec![('a', 2), ('b', 2)]);
+    assert_eq!(d.subscriber_count(), 2);
+}
+
+#[test]
+fn broadcasts_accept_references_to_stack_local_data() {
+    fn broadcast_local(d: &Delegate<'static, str>, prefix: &str) {
+        let local = format!("{prefix}-local");
+        d.broadcast(local.as_str());
+        d.broadcast(&*local);
+        d.broadcast_borrowed(&local);
+    }
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let seen_clone = seen.clone();
+    let d = Delegate::new();
+    d.subscribe(move |value: &str| {
+        seen_clone.lock().push(value.to_owned());
+        Response::StaySubscribed
+    });
+    broadcast_local(&d, "a");
+    assert_eq!(*seen.lock(), vec!["a-local", "a-local", "a-local"]);
+}
+
+#[test]
+fn broadcasts_accept_payloads_borrowing_stack_local_data() {
+    struct Event<'a> {
+        name: &'a str,
+        values: &'a [u32],
+    }
+    let mut seen = Vec::new();
+    {
+        let name = String::from("local");
+        let values = vec![1, 2, 3];
+        let d = Delegate::new();
+        d.subscribe(|event: &Event| {
+            seen.push((event.name.to_owned(), event.values.iter().sum::<u32>()));
+            Response::StaySubscribed
+        });
+        d.broadcast(Event {
+            name: &name,
+            values: &values,
+        });
+        d.broadcast(Event {
+            name: &name[..2],
+            values: &values[1..],
+        });
+    }
+    assert_eq!(
+        seen,
+        vec![(String::from("local"), 6), (String::from("lo"), 5)]
+    );
+}
+
+#[test]
+fn teardowns_run_in_reverse_order_on_drop() {
+    let torn_down = Arc::new(Mutex::new(Vec::new()));
+    let d = Delegate::<u32>::new();
+    let mut subscriptions = Vec::new();
+    for name in ['a', 'b', 'c'] {
+        let torn_down = torn_down.clone();
+        subscriptions.push(d.subscribe_with_teardown(
+            |_| Response::StaySubscribed,
+            move || torn_down.lock().push(name),
+        ));
+    }
+    d.subscribe(|_| Response::StaySubscribed);
+    d.unsubscribe(subscriptions.remove(1));
+    assert!(torn_down.lock().is_empty());
+    drop(d);
+    assert_eq!(*torn_down.lock(), vec!['c', 'a']);
+}
+
+#[test]
+fn counted_subscriptions_receive_invocation_count() {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let seen_clone = seen.clone();
+    let d = Delegate::new();
+    let counted = d.subscribe_counted(move |count, value: &u32| {
+        seen_clone.lock().push((count, *value));
+        Response::StaySubscribed
+    });
+    let muted = d.subscribe(|_| Response::StaySubscribed);
+    d.broadcast(10);
+    d.mute(&muted);
+    d.broadcast(20);
+    assert_eq!(*seen.lock(), vec![(1, 10), (2, 20)]);
+    assert_eq!(d.invocation_count_of(&counted), 2);
+    assert_eq!(d.invocation_count_of(&muted), 1);
+    d.unsubscribe(muted);
+}
+
+#[test]
+fn broadcast_collecting_gathers_all_errors() {
+    let d = Delegate::new();
+    let call_count = Arc::new(AtomicU64::new(0));
+    for divisor in [2, 3, 5] {
+        let call_count = call_count.clone();
+        d.subscribe_fallible(move |value: &u32| {
+            call_count.fetch_add(1, Ordering::SeqCst);
+            match value % divisor {
+                0 if *value > 10 => Ok(Response::CancelSubscription),
+                0 => Ok(Response::StaySubscribed),
+                _ => Err(format!("{value} is not a multiple of {divisor}")),
+            }
+        });
+    }
+    assert_eq!(
+        d.broadcast_collecting::<String, _>(10),
+        vec!["10 is not a multiple of 3"]
+    );
+    assert_eq!(
+        d.broadcast_collecting::<String, _>(7),
+        vec![
+            "7 is not a multiple of 2",
+            "7 is not a multiple of 3",
+            "7 is not a multiple of 5"
+        ]
+    );
+    assert!(d.broadcast_collecting::<String, _>(30).is_empty());
+    assert_eq!(d.subscriber_count(), 0);
+    assert!(d.broadcast_collecting::<&str, _>(1).is_empty());
+    assert_eq!(call_count.load(Ordering::SeqCst), 9);
+}
+
+#[test]
+fn fallible_subscribers_run_on_plain_broadcasts() {
+    let d = Delegate::new();
+    let call_count = Arc::new(AtomicU64::new(0));
+    let call_count_clone = call_count.clone();
+    d.subscribe_fallible(move |value: &u32| {
+        call_count_clone.fetch_add(1, Ordering::SeqCst);
+        match *value {
+            0 => Err("zero"),
+            _ => Ok(Response::CancelSubscription),
+        }
+    });
+    d.subscribe_with_broadcast_context(|_: &u32, _: &u32| Response::CancelSubscription);
+    d.broadcast(0);
+    assert_eq!(call_count.load(Ordering::SeqCst), 1);
+    assert_eq!(d.broadcast_collecting::<&str, _>(0), vec!["zero"]);
+    assert_eq!(d.subscriber_count(), 2);
+    d.broadcast(1);
+    assert_eq!(call_count.load(Ordering::SeqCst), 3);
+    assert_eq!(d.subscriber_count(), 1);
+}
+
+#[test]
+fn subscribe_while_alive_cancels_once_resource_is_gone() {
+    let alive = Arc::new(AtomicBool::new(true));
+    let call_count = Arc::new(AtomicU64::new(0));
+    let d = Delegate::new();
+    let alive_clone = alive.clone();
+    let call_count_clone = call_count.clone();
+    d.subscribe_while_alive(
+        move || alive_clone.load(Ordering::SeqCst),
+        move |_: &u32| {
+            call_count_clone.fetch_add(1, Ordering::SeqCst);
+            Response::StaySubscribed
+        },
+    );
+    d.broadcast(1);
+    d.broadcast(2);
+    alive.store(false, Ordering::SeqCst);
+    d.broadcast(3);
+    assert_eq!(call_count.load(Ordering::SeqCst), 2);
+    assert_eq!(d.subscriber_count(), 0);
+}
+
+#[test]
+fn deferred_unsubscriptions_outlive_nested_broadcasts() {
+    let d = Arc::new(ReentrantMutex::new(Delegate::new()));
+    let victim = Arc::new(Mutex::new(None));
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    {
+        let d_clone = d.clone();
+        let victim_clone = victim.clone();
+        d.lock().subscribe(move |value: &u32| {
+            if *value == 0 {
+                if let Some(subscription) = victim_clone.lock().take() {
+                    d_clone.lock().unsubscribe_deferred(subscription);
+                }
+                d_clone.lock().broadcast(1);
+            }
+            Response::StaySubscribed
+        });
+        let seen_clone = seen.clone();
+        *victim.lock() = Some(d.lock().subscribe(move |value| {
+            seen_clone.lock().push(*value);
+            Response::StaySubscribed
+        }));
+        d.lock().broadcast(0);
+        d.lock().broadcast(2);
+    }
+    assert_eq!(*seen.lock(), vec![1, 0]);
+}
+
+#[test]
+fn handle_unsubscriptions_outlive_nested_broadcasts() {
+    let d = Arc::new(ReentrantMutex::new(Delegate::new()));
+    let victim_id = Arc::new(AtomicU64::new(0));
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    {
+        let d_clone = d.clone();
+        d.lock().subscribe(move |value: &u32| {
+            if *value == 0 {
+                d_clone.lock().broadcast(1);
+            }
+            Response::StaySubscribed
+        });
+        let victim_id_clone = victim_id.clone();
+        d.lock().subscribe_with_handle(move |value, handle| {
+            if *value == 1 {
+                handle.unsubscribe(victim_id_clone.load(Ordering::SeqCst));
+            }
+            Response::StaySubscribed
+        });
+        let seen_clone = seen.clone();
+        let victim = d.lock().subscribe(move |value| {
+            seen_clone.lock().push(*value);
+            Response::StaySubscribed
+        });
+        victim_id.store(victim.id(), Ordering::SeqCst);
+        d.lock().broadcast(0);
+        d.lock().broadcast(2);
+    }
+    assert_eq!(*seen.lock(), vec![1, 0]);
+}
+
+#[test]
+fn dispatch_round_robin_rotates_through_subscribers() {
+    let d = Delegate::new();
+    assert!(!d.dispatch_round_robin(0));
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let seen_clone = seen.clone();
+    d.subscribe(move |value: &u32| {
+        seen_clone.lock().push(('a', *value));
+        Response::StaySubscribed
+    });
+    let seen_clone = seen.clone();
+    d.subscribe(move |value: &u32| {
+        seen_clone.lock().push(('b', *value));
+        Response::CancelSubscription
+    });
+    let seen_clone = seen.clone();
+    d.subscribe(move |value: &u32| {
+        seen_clone.lock().push(('c', *value));
+        Response::StaySubscribed
+    });
+    for value in 1..=5 {
+        assert!(d.dispatch_round_robin(value));
+    }
+    assert_eq!(
+        *seen.lock(),
+        vec![('a', 1), ('b', 2), ('c', 3), ('a', 4), ('c', 5)]
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn collect_next_records_values_then_unsubscribes() {
+    let d = Delegate::new();
+    let none = d.collect_next(0);
+    let values = d.collect_next(3);
+    d.broadcast(1);
+    d.broadcast(2);
+    assert_eq!(*values.lock().unwrap(), vec![1, 2]);
+    d.broadcast(3);
+    d.broadcast(4);
+    assert_eq!(*values.lock().unwrap(), vec![1, 2, 3]);
+    assert!(none.lock().unwrap().is_empty());
+    assert_eq!(d.subscriber_count(), 0);
+}
+
+#[test]
+fn broadcast_with_skips_building_value_without_subscribers() {
+    let d = Delegate::new();
+    let mut built = 0;
+    d.broadcast_with(|| {
+        built += 1;
+        1
+    });
+    assert_eq!(built, 0);
+    d.subscribe(|_| Response::CancelSubscription);
+    d.broadcast_with(|| {
+        built += 1;
+        2
+    });
+    assert_eq!(built, 1);
+}
+
+#[test]
+fn tuple_payloads_can_be_destructured_by_callbacks() {
+    let pairs = Delegate::new();
+    let triples = Delegate::new();
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let seen_clone = seen.clone();
+    pairs.subscribe2(move |a: &u32, b: &u32| {
+        seen_clone.lock().push(a + b);
+        Response::StaySubscribed
+    });
+    let seen_clone = seen.clone();
+    triples.subscribe3(move |a: &u32, b: &u32, c: &u32| {
+        seen_clone.lock().push(a * b * c);
+        Response::StaySubscribed
+    });
+    pairs.broadcast((1, 2));
+    triples.broadcast((2, 3, 4));
+    assert_eq!(*seen.lock(), vec![3, 24]);
+}
+
+#[test]
+fn can_broadcast_unsized_values() {
+    let bytes = Delegate::<[u8]>::new();
+    let text = Delegate::<str>::new();
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let seen_clone = seen.clone();
+    bytes.subscribe(move |slice| {
+        seen_clone.lock().push(slice.len());
+        Response::StaySubscribed
+    });
+    let seen_clone = seen.clone();
+    text.subscribe(move |string| {
+        seen_clone.lock().push(string.len());
+        Response::StaySubscribed
+    });
+    let buffer = vec![0u8; 8];
+    bytes.broadcast(&buffer[2..]);
+    bytes.broadcast(buffer);
+    text.broadcast("hello");
+    text.broadcast(String::from("hi"));
+    assert_eq!(*seen.lock(), vec![6, 8, 5, 2]);
+}
+
+#[test]
+fn cleanup_runs_once_when_subscription_ends() {
+    let ended = Arc::new(Mutex::new(Vec::new()));
+    {
+        let d = Delegate::new();
+        let ended_clone = ended.clone();
+        d.subscribe_with_cleanup(
+            |_: &u32| Response::CancelSubscription,
+            move || ended_clone.lock().push("cancelled"),
+        );
+        let ended_clone = ended.clone();
+        let s = d.subscribe_with_cleanup(
+            |_| Response::StaySubscribed,
+            move || ended_clone.lock().push("unsubscribed"),
+        );
+        let ended_clone = ended.clone();
+        d.subscribe_with_cleanup(
+            |_| Response::StaySubscribed,
+            move || ended_clone.lock().push("dropped"),
+        );
+        d.broadcast(1);
+        d.broadcast(2);
+        d.unsubscribe(s);
+        assert_eq!(*ended.lock(), vec!["cancelled", "unsubscribed"]);
+    }
+    assert_eq!(*ended.lock(), vec!["cancelled", "unsubscribed", "dropped"]);
+}
+
+#[cfg(feature = "futures")]
+#[test]
+fn stream_yields_broadcast_values() {
+    use futures::{executor, StreamExt};
+
+    let d = Delegate::new();
+    let stream = d.into_stream();
+    for value in 0..5 {
+        d.broadcast(value);
+    }
+    let values: Vec<u32> = executor::block_on(stream.map(|v| v * 10).take(3).collect());
+    assert_eq!(values, vec![0, 10, 20]);
+    d.broadcast(5);
+    assert_eq!(d.subscriber_count(), 0);
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+fn can_list_subscription_ids() {
+    let d = Delegate::new();
+    let a = d.subscribe(|_: &u32| Response::CancelSubscription);
+    let b = d.subscribe(|_| Response::StaySubscribed);
+    assert_eq!(d.subscription_ids(), vec![a.id(), b.id()]);
+    d.broadcast(1);
+    assert_eq!(d.subscription_ids(), vec![b.id()]);
+    assert!(!d.contains(a.id()));
+    assert!(d.contains(b.id()));
+}
+
+#[test]
+fn cancel_if_cancels_when_condition_holds() {
+    let d = Delegate::new();
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let seen_clone = seen.clone();
+    d.subscribe(move |value: &u32| {
+        seen_clone.lock().push(*value);
+        Response::cancel_if(*value >= 2)
+    });
+    d.broadcast(1);
+    d.broadcast(2);
+    d.broadcast(3);
+    assert_eq!(*seen.lock(), vec![1, 2]);
+}
+
+#[test]
+fn handle_unsubscribes_other_callbacks_after_broadcast() {
+    let d = Delegate::new();
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let seen_clone = seen.clone();
+    let target = d.subscribe(move |value: &u32| {
+        seen_clone.lock().push(*value);
+        Response::StaySubscribed
+    });
+    let target_id = target.id();
+    d.subscribe_with_handle(move |value, handle| {
+        if *value == 2 {
+            handle.unsubscribe(target_id);
+        }
+        Response::StaySubscribed
+    });
+    d.broadcast(1);
+    d.broadcast(2);
+    d.broadcast(3);
+    assert_eq!(*seen.lock(), vec![1, 2]);
+    assert_eq!(d.subscriber_count(), 1);
+}
+
+#[test]
+fn mutable_callbacks_form_a_pipeline() {
+    let d = Delegate::new();
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let seen_clone = seen.clone();
+    d.subscribe(move |value: &Vec<u32>| {
+        seen_clone.lock().push(value.clone());
+        Response::StaySubscribed
+    });
+    d.subscribe_mut(|value| {
+        value.push(1);
+        Response::StaySubscribed
+    });
+    d.subscribe_mut(|value| {
+        value.push(2);
+        Response::CancelSubscription
+    });
+    let mut value = Vec::new();
+    d.broadcast_mut(&mut value);
+    d.broadcast_mut(&mut value);
+    assert_eq!(value, vec![1, 2, 1]);
+    d.broadcast(&value);
+    assert_eq!(*seen.lock(), vec![vec![1, 2, 1]]);
+}
+
+#[test]
+fn generation_subscribers_fire_once_per_generation() {
+    let generation = Arc::new(AtomicU64::new(0));
+    let a = Delegate::new();
+    let b = Delegate::new();
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let seen_clone = seen.clone();
+    a.subscribe_on_generation(&generation, move |value: &u32| {
+        seen_clone.lock().push(*value);
+        Response::StaySubscribed
+    });
+    let seen_clone = seen.clone();
+    b.subscribe_on_generation(&generation, move |value: &u32| {
+        seen_clone.lock().push(*value);
+        Response::StaySubscribed
+    });
+    a.broadcast(1);
+    a.broadcast(2);
+    b.broadcast(3);
+    generation.fetch_add(1, Ordering::SeqCst);
+    a.broadcast(4);
+    b.broadcast(5);
+    b.broadcast(6);
+    assert_eq!(*seen.lock(), vec![1, 3, 4, 5]);
+}
+
+#[test]
+fn shared_callbacks_can_be_cloned_into_other_delegates() {
+    let template = Delegate::new();
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let seen_clone = seen.clone();
+    template.subscribe_arc(Arc::new(move |value: &u32| {
+        seen_clone.lock().push(*value);
+        Response::StaySubscribed
+    }));
+    template.subscribe(|_| Response::StaySubscribed);
+    let target = Delegate::new();
+    let subscriptions = template.clone_subscriptions_into(&target);
+    assert_eq!(subscriptions.len(), 1);
+    assert_eq!(target.subscriber_count(), 1);
+    template.broadcast(1);
+    target.broadcast(2);
+    assert_eq!(*seen.lock(), vec![1, 2]);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn channel_feeds_worker_thread() {
+    let d = Delegate::new();
+    let receiver = d.subscribe_channel();
+    let worker = std::thread::spawn(move || receiver.iter().take(2).sum::<u32>());
+    d.broadcast(3);
+    d.broadcast(4);
+    assert_eq!(worker.join().unwrap(), 7);
+    d.broadcast(5);
+    assert_eq!(d.subscriber_count(), 0);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn from_sender_forwards_until_receiver_is_dropped() {
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let d = Delegate::from_sender(sender);
+    d.broadcast(3);
+    d.broadcast(4);
+    assert_eq!(receiver.try_iter().collect::<Vec<_>>(), vec![3, 4]);
+    drop(receiver);
+    d.broadcast(5);
+    assert_eq!(d.subscriber_count(), 0);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn expiring_subscriptions_cancel_on_next_broadcast() {
+    let call_count = Arc::new(AtomicU64::new(0));
+    let d = Delegate::new();
+    let call_count_clone = call_count.clone();
+    d.subscribe_expiring(std::time::Duration::from_millis(20), move |_: &()| {
+        call_count_clone.fetch_add(1, Ordering::SeqCst);
+        Response::StaySubscribed
+    });
+    d.broadcast(());
+    std::thread::sleep(std::time::Duration::from_millis(40));
+    assert_eq!(d.subscriber_count(), 1);
+    d.broadcast(());
+    assert_eq!(call_count.load(Ordering::SeqCst), 1);
+    assert_eq!(d.subscriber_count(), 0);
+}
+
+#[test]
+fn cancellable_broadcast_stops_once_flag_is_set() {
+    let cancel = Arc::new(AtomicBool::new(false));
+    let call_count = Arc::new(AtomicU64::new(0));
+    let d = Delegate::new();
+    for _ in 0..3 {
+        let cancel = cancel.clone();
+        let call_count = call_count.clone();
+        d.subscribe(move |_: &()| {
+            if call_count.fetch_add(1, Ordering::SeqCst) == 1 {
+                cancel.store(true, Ordering::SeqCst);
+            }
+            Response::StaySubscribed
+        });
+    }
+    d.broadcast_cancellable((), &cancel);
+    assert_eq!(call_count.load(Ordering::SeqCst), 2);
+    assert_eq!(d.subscriber_count(), 3);
+    cancel.store(false, Ordering::SeqCst);
+    d.broadcast_cancellable((), &cancel);
+    assert_eq!(call_count.load(Ordering::SeqCst), 5);
+}
+
+#[test]
+fn subscriptions_are_ordered_by_id() {
+    let d = Delegate::<()>::new();
+    let a = d.subscribe(|_| Response::StaySubscribed);
+    let b = d.subscribe(|_| Response::StaySubscribed);
+    assert!(a < b);
+    let ids: Vec<u64> = std::collections::BTreeSet::from([b, a])
+        .iter()
+        .map(|s| s.id())
+        .collect();
+    assert!(ids[0] < ids[1]);
+}
+
+#[test]
+fn broadcast_borrowed_works_in_generic_code() {
+    fn forward<T: Clone + Send + 'static>(d: &Delegate<T>, values: &[T]) {
+        for value in values {
+            d.broadcast_borrowed(value);
+        }
+    }
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let d = Delegate::new();
+    let seen_clone = seen.clone();
+    d.subscribe(move |value: &String| {
+        seen_clone.lock().push(value.clone());
+        Response::StaySubscribed
+    });
+    forward(&d, &[String::from("a"), String::from("b")]);
+    assert_eq!(*seen.lock(), vec!["a", "b"]);
+}
+
+#[test]
+fn broadcast_cow_accepts_borrowed_and_owned_values() {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let d = Delegate::<[u32]>::new();
+    let seen_clone = seen.clone();
+    d.subscribe(move |values| {
+        seen_clone.lock().push(values.len());
+        Response::StaySubscribed
+    });
+    d.broadcast_cow(Cow::Borrowed(&[1, 2][..]));
+    d.broadcast_cow(Cow::Owned(vec![1, 2, 3]));
+    assert_eq!(*seen.lock(), vec![2, 3]);
+}
+
+#[test]
+fn muted_subscriptions_are_skipped_but_kept() {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let d = Delegate::new();
+    let make_callback = |name: &'static str| {
+        let seen = seen.clone();
+        move |_: &()| {
+            seen.lock().push(name);
+            Response::StaySubscribed
+        }
+    };
+    let a = d.subscribe(make_callback("a"));
+    d.subscribe(make_callback("b"));
+    d.mute(&a);
+    d.notify();
+    assert!(d.is_subscribed(&a));
+    assert!(d.is_muted(&a));
+    d.unmute(&a);
+    d.notify();
+    assert_eq!(*seen.lock(), vec!["b", "a", "b"]);
+}
+
+#[test]
+fn broadcast_context_reaches_matching_subscribers_only() {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let d = Delegate::new();
+    let seen_clone = seen.clone();
+    d.subscribe_with_broadcast_context(move |source: &&str, value: &u32| {
+        seen_clone.lock().push(format!("{source}:{value}"));
+        Response::StaySubscribed
+    });
+    let seen_clone = seen.clone();
+    d.subscribe(move |value| {
+        seen_clone.lock().push(format!("plain:{value}"));
+        Response::StaySubscribed
+    });
+    d.broadcast_with_context(1, &"player");
+    d.broadcast_with_context(2, &42u8);
+    d.broadcast(3);
+    assert_eq!(
+        *seen.lock(),
+        vec!["player:1", "plain:1", "plain:2", "plain:3"]
+    );
+}
+
+#[test]
+fn delegate_can_remember_last_broadcast() {
+    let d = Delegate::new();
+    d.broadcast(1);
+    d.remember_last_broadcast();
+    assert_eq!(d.last_broadcast(), None);
+    d.broadcast(2);
+    d.broadcast(3);
+    assert_eq!(d.last_broadcast(), Some(3));
+}
+
+#[test]
+fn last_broadcast_reflects_changes_made_by_mutable_callbacks() {
+    let d = Delegate::new();
+    d.remember_last_broadcast();
+    d.subscribe_mut(|value: &mut u32| {
+        *value *= 10;
+        Response::StaySubscribed
+    });
+    let mut value = 2;
+    d.broadcast_mut(&mut value);
+    assert_eq!(d.last_broadcast(), Some(20));
+}
+
+#[test]
+fn broadcast_rev_executes_latest_callbacks_first() {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let d = Delegate::new();
+    for name in ["a", "b", "c"] {
+        let seen = seen.clone();
+        d.subscribe(move |_: &()| {
+            seen.lock().push(name);
+            Response::StaySubscribed
+        });
+    }
+    d.broadcast_rev(());
+    d.broadcast(());
+    assert_eq!(*seen.lock(), vec!["c", "b", "a", "a", "b", "c"]);
+}
@@ -0,0 +1,53 @@
+use parking_lot::Mutex;
+use std::sync::Arc;
+
+use squeak::{EventBus, Response};
+
+struct DamageReceived(u32);
+struct Healed(u32);
+
+#[test]
+fn event_bus_dispatches_by_event_type() {
+    let damage = Arc::new(Mutex::new(0));
+    let heals = Arc::new(Mutex::new(0));
+    {
+        let events = EventBus::new();
+        let damage_clone = damage.clone();
+        events.subscribe(move |event: &DamageReceived| {
+            *damage_clone.lock() += event.0;
+            Response::StaySubscribed
+        });
+        let heals_clone = heals.clone();
+        events.subscribe(move |event: &Healed| {
+            *heals_clone.lock() += event.0;
+            Response::StaySubscribed
+        });
+        events.emit(DamageReceived(5));
+        events.emit(DamageReceived(3));
+        events.emit(Healed(2));
+    }
+    assert_eq!(*damage.lock(), 8);
+    assert_eq!(*heals.lock(), 2);
+}
+
+#[test]
+fn event_bus_does_not_execute_unsubscribed_callbacks() {
+    let call_count = Arc::new(Mutex::new(0));
+    {
+        let events = EventBus::new();
+        let call_count_clone = call_count.clone();
+        let subscription = events.subscribe(move |_: &Healed| {
+            *call_count_clone.lock() += 1;
+            Response::StaySubscribed
+        });
+        events.unsubscribe::<Healed>(subscription);
+        events.emit(Healed(2));
+    }
+    assert_eq!(*call_count.lock(), 0);
+}
+
+#[test]
+fn can_emit_events_without_subscribers() {
+    let events = EventBus::new();
+    events.emit(DamageReceived(5));
+}
@@ -0,0 +1,22 @@
+#![cfg(feature = "std")]
+
+use std::{sync::Arc, thread};
+
+use parking_lot::Mutex;
+use squeak::Response;
+
+#[test]
+fn global_delegates_are_shared_within_a_thread() {
+    struct Ping(u32);
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let seen_clone = seen.clone();
+    squeak::global::<Ping>().subscribe(move |ping| {
+        seen_clone.lock().push(ping.0);
+        Response::StaySubscribed
+    });
+    squeak::global::<Ping>().broadcast(Ping(1));
+    thread::spawn(|| squeak::global::<Ping>().broadcast(Ping(2)))
+        .join()
+        .unwrap();
+    assert_eq!(*seen.lock(), vec![1]);
+}
@@ -0,0 +1,62 @@
+use squeak::{lens, Lens, Observable, Response};
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Stats {
+    health: u32,
+    mana: u32,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Player {
+    name: String,
+    stats: Stats,
+}
+
+#[test]
+fn focused_mutations_broadcast_whole_value() {
+    let mut seen_values = Vec::new();
+    {
+        let mut player = Observable::new(Player::default());
+        player.subscribe(|p| {
+            seen_values.push(p.clone());
+            Response::StaySubscribed
+        });
+        {
+            let mut mana = player.focus(lens!(Player, stats)).focus(lens!(Stats, mana));
+            mana.mutate(|m| *m = 30);
+            assert_eq!(*mana, 30);
+        }
+        player
+            .focus(lens!(Player, stats.health))
+            .mutate(|h| *h = 100);
+    }
+    assert_eq!(
+        seen_values,
+        vec![
+            Player {
+                name: String::new(),
+                stats: Stats {
+                    health: 0,
+                    mana: 30
+                }
+            },
+            Player {
+                name: String::new(),
+                stats: Stats {
+                    health: 100,
+                    mana: 30
+                }
+            },
+        ]
+    );
+}
+
+#[test]
+fn lenses_compose() {
+    let stats = Lens::new(|p: &Player| &p.stats, |p| &mut p.stats);
+    let health = stats.then(lens!(Stats, health));
+    let mut player = Player::default();
+    *health.get_mut(&mut player) = 42;
+    assert_eq!(*health.get(&player), 42);
+    assert_eq!(player.stats.health, 42);
+}
@@ -0,0 +1,38 @@
+#![cfg(feature = "log")]
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use parking_lot::Mutex;
+
+use squeak::Observable;
+
+static MESSAGES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+struct RecordingLogger;
+
+impl Log for RecordingLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= Level::Debug
+    }
+
+    fn log(&self, record: &Record) {
+        MESSAGES.lock().push(record.args().to_string());
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: RecordingLogger = RecordingLogger;
+
+#[test]
+fn watch_logs_previous_and_updated_values() {
+    log::set_logger(&LOGGER).unwrap();
+    log::set_max_level(LevelFilter::Debug);
+
+    let mut o = Observable::new(1u8);
+    let watch = o.watch();
+    o.mutate(|value| *value = 2);
+    o.mutate(|value| *value = 3);
+    o.unsubscribe(watch);
+    o.mutate(|value| *value = 4);
+    assert_eq!(*MESSAGES.lock(), vec!["u8: 1 -> 2", "u8: 2 -> 3"]);
+}
@@ -0,0 +1,33 @@
+use squeak::{Observable, Response};
+
+#[test]
+fn mapped_observable_projects_without_cloning() {
+    struct NotClone(u32);
+    let mut seen_values = Vec::new();
+    {
+        let mut o = Observable::new((NotClone(0), String::from("a")));
+        let first = o.map_ref(|(first, _)| first);
+        first.subscribe(|value| {
+            seen_values.push(value.0);
+            Response::StaySubscribed
+        });
+        assert_eq!(first.peek().0, 0);
+        o.mutate(|value| value.0 .0 = 1);
+        o.mutate(|value| value.1.push('b'));
+    }
+    assert_eq!(seen_values, vec![1, 1]);
+}
+
+#[test]
+fn mapped_observable_supports_unsized_projections() {
+    let mut seen_values = Vec::new();
+    {
+        let mut o = Observable::new(String::from("hello"));
+        o.map_ref(|s| s.as_str()).subscribe(|s: &str| {
+            seen_values.push(s.len());
+            Response::StaySubscribed
+        });
+        o.mutate(|s| s.push('!'));
+    }
+    assert_eq!(seen_values, vec![6]);
+}
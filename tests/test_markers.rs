@@ -0,0 +1,26 @@
+use std::rc::Rc;
+
+use squeak::{CancellationToken, Delegate, Observable, SharedObservable, Subscription};
+
+fn assert_send<T: Send>() {}
+fn assert_sync<T: Sync>() {}
+
+#[test]
+fn delegates_are_send_for_any_payload() {
+    assert_send::<Delegate<u32>>();
+    assert_send::<Delegate<Rc<u32>>>();
+}
+
+#[test]
+fn observables_are_send_when_value_is_send() {
+    assert_send::<Observable<u32>>();
+    assert_send::<SharedObservable<u32>>();
+}
+
+#[test]
+fn subscriptions_and_tokens_are_send_and_sync() {
+    assert_send::<Subscription>();
+    assert_sync::<Subscription>();
+    assert_send::<CancellationToken>();
+    assert_sync::<CancellationToken>();
+}
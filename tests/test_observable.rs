@@ -1,4 +1,8 @@
-use squeak::{Observable, Response};
+use parking_lot::Mutex;
+use std::collections::{BTreeSet, HashSet};
+use std::sync::Arc;
+
+use squeak::{CollectionDiff, CrossDirection, Observable, Response, SubscribeOptions};
 
 #[test]
 fn observable_broadcasts_new_values() {
@@ -29,3 +33,971 @@ fn observable_no_longer_notifies_after_unsubscribe() {
     }
     assert_eq!(call_count, 1);
 }
+
+#[test]
+fn observable_converts_to_and_from_inner_value() {
+    let mut call_count = 0;
+    let value = {
+        let mut o: Observable<_> = 100.into();
+        o.subscribe(|_| {
+            call_count += 1;
+            Response::StaySubscribed
+        });
+        o.mutate(|value| *value += 1);
+        o.into_inner()
+    };
+    assert_eq!(value, 101);
+    assert_eq!(call_count, 1);
+}
+
+#[test]
+fn subscribe_with_snapshot_returns_current_value() {
+    let mut seen_value = 0;
+    let snapshot = {
+        let mut o = Observable::new(10);
+        let (_, snapshot) = o.subscribe_with_snapshot(|new_value| {
+            seen_value = *new_value;
+            Response::StaySubscribed
+        });
+        o.mutate(|value| *value = 42);
+        snapshot
+    };
+    assert_eq!(snapshot, 10);
+    assert_eq!(seen_value, 42);
+}
+
+#[test]
+fn subscribe_times_stops_after_n_mutations() {
+    let mut call_count = 0;
+    {
+        let mut o = Observable::new(0);
+        o.subscribe_times(3, |_| call_count += 1);
+        for _ in 0..5 {
+            o.mutate(|value| *value += 1);
+        }
+    }
+    assert_eq!(call_count, 3);
+}
+
+#[test]
+fn subscribe_zero_times_never_fires() {
+    let mut call_count = 0;
+    {
+        let mut o = Observable::new(0);
+        o.subscribe_times(0, |_| call_count += 1);
+        o.mutate(|value| *value += 1);
+    }
+    assert_eq!(call_count, 0);
+}
+
+#[test]
+fn subscribe_opts_combines_behaviors() {
+    let seen_values = Arc::new(Mutex::new(Vec::new()));
+    {
+        let mut o = Observable::new(0);
+        let make_callback = |name: &'static str| {
+            let seen_values = seen_values.clone();
+            move |new_value: &i32| {
+                seen_values.lock().push((name, *new_value));
+                Response::StaySubscribed
+            }
+        };
+        o.subscribe(make_callback("default"));
+        let options = SubscribeOptions {
+            fire_immediately: true,
+            ..Default::default()
+        };
+        o.subscribe_opts(options, make_callback("immediate"));
+        let options = SubscribeOptions {
+            priority: 1,
+            once: true,
+            ..Default::default()
+        };
+        o.subscribe_opts(options, make_callback("once"));
+        o.mutate(|value| *value += 1);
+        o.mutate(|value| *value += 1);
+    }
+    assert_eq!(
+        *seen_values.lock(),
+        vec![
+            ("immediate", 0),
+            ("once", 1),
+            ("default", 1),
+            ("immediate", 1),
+            ("default", 2),
+            ("immediate", 2),
+        ]
+    );
+}
+
+#[test]
+fn set_silently_does_not_notify_until_requested() {
+    let mut seen_values = Vec::new();
+    {
+        let mut o = Observable::new(0);
+        o.subscribe(|new_value| {
+            seen_values.push(*new_value);
+            Response::StaySubscribed
+        });
+        o.set_silently(1);
+        o.set_silently(2);
+        o.notify_current();
+    }
+    assert_eq!(seen_values, vec![2]);
+}
+
+#[test]
+fn value_mut_does_not_notify_until_requested() {
+    let mut seen_values = Vec::new();
+    {
+        let mut o = Observable::new(vec![0]);
+        o.subscribe(|new_value| {
+            seen_values.push(new_value.clone());
+            Response::StaySubscribed
+        });
+        o.value_mut().push(1);
+        o.value_mut()[0] = 2;
+        o.notify_current();
+    }
+    assert_eq!(seen_values, vec![vec![2, 1]]);
+}
+
+#[test]
+fn index_guard_notifies_on_drop() {
+    let mut seen_values = Vec::new();
+    {
+        let mut o = Observable::new(vec![1, 2, 3]);
+        o.subscribe(|new_value| {
+            seen_values.push(new_value.clone());
+            Response::StaySubscribed
+        });
+        {
+            let mut element = o.get_index_mut(1);
+            *element *= 10;
+            *element += 1;
+        }
+        assert_eq!(*o, vec![1, 21, 3]);
+    }
+    assert_eq!(seen_values, vec![vec![1, 21, 3]]);
+}
+
+#[test]
+fn version_counts_mutations() {
+    let mut seen_versions = Vec::new();
+    {
+        let mut o = Observable::new(0);
+        o.subscribe_with_version(|version, new_value| {
+            seen_versions.push((version, *new_value));
+            Response::StaySubscribed
+        });
+        o.mutate(|value| *value += 1);
+        o.set_silently(5);
+        o.notify_current();
+        o.mutate(|value| *value += 1);
+        o.mutate_tagged("script", |value| *value += 1);
+        assert_eq!(o.version(), 4);
+    }
+    assert_eq!(seen_versions, vec![(1, 1), (2, 5), (3, 6), (4, 7)]);
+}
+
+#[test]
+fn extend_notify_broadcasts_once() {
+    let mut seen_values = Vec::new();
+    {
+        let mut o = Observable::new(String::from("a"));
+        o.subscribe(|new_value| {
+            seen_values.push(new_value.clone());
+            Response::StaySubscribed
+        });
+        o.extend_notify(['b', 'c']);
+    }
+    assert_eq!(seen_values, vec!["abc"]);
+}
+
+#[test]
+fn key_change_subscribers_ignore_other_changes() {
+    let mut seen_values = Vec::new();
+    {
+        let mut o = Observable::new((1, 'a'));
+        o.subscribe_on_key_change(
+            |(level, _)| *level,
+            |new_value| {
+                seen_values.push(*new_value);
+                Response::StaySubscribed
+            },
+        );
+        o.mutate(|value| value.1 = 'b');
+        o.mutate(|value| value.0 = 2);
+        o.mutate(|value| *value = (2, 'c'));
+        o.mutate(|value| value.0 = 1);
+    }
+    assert_eq!(seen_values, vec![(2, 'b'), (1, 'c')]);
+}
+
+#[test]
+fn mutate_many_broadcasts_after_each_item() {
+    let mut seen_values = Vec::new();
+    {
+        let mut o = Observable::new(0);
+        o.subscribe(|new_value| {
+            seen_values.push(*new_value);
+            Response::StaySubscribed
+        });
+        o.mutate_many([1, 2, 3], |value, item| *value += item);
+    }
+    assert_eq!(seen_values, vec![1, 3, 6]);
+}
+
+#[test]
+fn tagged_subscribers_only_see_tagged_mutations() {
+    let mut seen_values = Vec::new();
+    let mut seen_tags = Vec::new();
+    {
+        let mut o = Observable::new(0);
+        o.subscribe(|new_value| {
+            seen_values.push(*new_value);
+            Response::StaySubscribed
+        });
+        o.subscribe_tagged_changes(|tag: &&str, new_value| {
+            seen_tags.push((*tag, *new_value));
+            Response::StaySubscribed
+        });
+        o.mutate_tagged("user", |value| *value = 1);
+        o.mutate(|value| *value = 2);
+        o.mutate_tagged(7_u8, |value| *value = 3);
+        o.mutate_tagged("script", |value| *value = 4);
+    }
+    assert_eq!(seen_values, vec![1, 2, 3, 4]);
+    assert_eq!(seen_tags, vec![("user", 1), ("script", 4)]);
+}
+
+#[test]
+fn edge_subscribers_only_see_transitions() {
+    let mut rising = 0;
+    let mut falling = 0;
+    {
+        let mut o = Observable::new(true);
+        o.subscribe_rising_edge(|| {
+            rising += 1;
+            Response::StaySubscribed
+        });
+        o.subscribe_falling_edge(|| {
+            falling += 1;
+            Response::StaySubscribed
+        });
+        for value in [true, false, false, true, true, false] {
+            o.mutate(|v| *v = value);
+        }
+    }
+    assert_eq!(rising, 1);
+    assert_eq!(falling, 2);
+}
+
+#[test]
+fn failed_transactions_restore_value_silently() {
+    let mut seen_values = Vec::new();
+    {
+        let mut o = Observable::new(vec![1, 2]);
+        o.subscribe(|new_value| {
+            seen_values.push(new_value.clone());
+            Response::StaySubscribed
+        });
+        let result: Result<(), &str> = o.transaction(|value| {
+            value.push(3);
+            value[0] = 10;
+            Err("invalid")
+        });
+        assert_eq!(result, Err("invalid"));
+        assert_eq!(*o, vec![1, 2]);
+        let result: Result<usize, ()> = o.transaction(|value| {
+            value.push(3);
+            value[0] = 10;
+            Ok(value.len())
+        });
+        assert_eq!(result, Ok(3));
+    }
+    assert_eq!(seen_values, vec![vec![10, 2, 3]]);
+}
+
+#[test]
+fn mutate_delta_measures_change_and_broadcasts() {
+    let mut seen_values = Vec::new();
+    let delta;
+    {
+        let mut o = Observable::new(vec![1, 2]);
+        o.subscribe(|new_value| {
+            seen_values.push(new_value.len());
+            Response::StaySubscribed
+        });
+        delta = o.mutate_delta(|v| v.iter().sum::<i32>(), |v| v.extend([3, 4]));
+    }
+    assert_eq!(delta, 7);
+    assert_eq!(seen_values, vec![4]);
+}
+
+#[test]
+fn delta_subscribers_ignore_small_changes() {
+    let mut seen_values = Vec::new();
+    {
+        let mut o = Observable::new(10_u32);
+        o.subscribe_on_delta(5, |new_value| {
+            seen_values.push(*new_value);
+            Response::StaySubscribed
+        });
+        for value in [12, 14, 15, 11, 10, 9, 21] {
+            o.mutate(|v| *v = value);
+        }
+    }
+    assert_eq!(seen_values, vec![15, 10, 21]);
+}
+
+#[test]
+fn set_if_only_broadcasts_when_predicate_holds() {
+    let mut seen_values = Vec::new();
+    {
+        let mut o = Observable::new(1);
+        o.subscribe(|new_value| {
+            seen_values.push(*new_value);
+            Response::StaySubscribed
+        });
+        assert!(!o.set_if(|v| *v > 5, 10));
+        assert_eq!(*o, 1);
+        assert!(o.set_if(|v| *v < 5, 10));
+        assert_eq!(*o, 10);
+    }
+    assert_eq!(seen_values, vec![10]);
+}
+
+#[test]
+fn every_nth_subscribers_skip_mutations() {
+    let mut every_third = Vec::new();
+    let mut every_one = 0;
+    let mut never = 0;
+    {
+        let mut o = Observable::new(0);
+        o.subscribe_every_nth(3, |new_value| {
+            every_third.push(*new_value);
+            Response::StaySubscribed
+        });
+        o.subscribe_every_nth(1, |_| {
+            every_one += 1;
+            Response::StaySubscribed
+        });
+        o.subscribe_every_nth(0, |_| {
+            never += 1;
+            Response::StaySubscribed
+        });
+        for _ in 0..7 {
+            o.mutate(|v| *v += 1);
+        }
+    }
+    assert_eq!(every_third, vec![3, 6]);
+    assert_eq!(every_one, 7);
+    assert_eq!(never, 0);
+}
+
+#[test]
+fn subscribe_as_into_converts_values() {
+    let mut seen_values: Vec<Option<&str>> = Vec::new();
+    {
+        let mut o = Observable::new("a");
+        o.subscribe_as_into(|value| {
+            seen_values.push(value);
+            Response::StaySubscribed
+        });
+        o.mutate(|v| *v = "b");
+    }
+    assert_eq!(seen_values, vec![Some("b")]);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn change_log_keeps_most_recent_changes() {
+    let mut o = Observable::new(0);
+    o.mutate(|v| *v = 1);
+    assert!(o.change_log().is_empty());
+    o.record_changes(2);
+    for value in 2..=4 {
+        o.mutate(|v| *v = value);
+    }
+    let values: Vec<i32> = o.change_log().iter().map(|r| r.value).collect();
+    assert_eq!(values, vec![3, 4]);
+    assert!(o.change_log()[0].at <= o.change_log()[1].at);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn mirror_into_tracks_value_until_unsubscribed() {
+    let mut o = Observable::new(vec![1]);
+    let shadow = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let subscription = o.mirror_into(shadow.clone());
+    assert_eq!(*shadow.lock().unwrap(), vec![1]);
+    o.mutate(|v| v.push(2));
+    assert_eq!(*shadow.lock().unwrap(), vec![1, 2]);
+    o.unsubscribe(subscription);
+    o.mutate(|v| v.push(3));
+    assert_eq!(*shadow.lock().unwrap(), vec![1, 2]);
+}
+
+#[test]
+fn saturating_mutations_broadcast_clamped_value() {
+    let mut seen_values = Vec::new();
+    {
+        let mut o = Observable::new(i8::MAX - 1);
+        o.subscribe(|new_value| {
+            seen_values.push(*new_value);
+            Response::StaySubscribed
+        });
+        o.mutate_saturating_add(5);
+        o.mutate_saturating_add(1);
+        o.mutate_saturating_sub(i8::MAX);
+        o.mutate_saturating_sub(i8::MAX);
+        o.mutate_saturating_sub(10);
+    }
+    assert_eq!(seen_values, vec![i8::MAX, i8::MAX, 0, -i8::MAX, i8::MIN]);
+}
+
+#[test]
+fn debug_snapshot_counts_all_kinds_of_subscribers() {
+    let o = Observable::new(String::from("goblin"));
+    o.subscribe(|_| Response::StaySubscribed);
+    o.subscribe_coalesced(|_| Response::StaySubscribed);
+    o.subscribe_sampled(|_| Response::StaySubscribed);
+    assert_eq!(o.debug_snapshot(), (String::from("goblin"), 3));
+}
+
+#[test]
+fn plain_observables_do_not_allocate_extra_subscribers() {
+    let plain = Observable::new(0_u32);
+    plain.subscribe(|_| Response::StaySubscribed);
+    let hooked = Observable::new(0_u32);
+    hooked.add_pre_hook(|_| {});
+    assert!(hooked.estimated_size() > plain.estimated_size());
+}
+
+#[test]
+fn try_mutate_hooks_are_balanced() {
+    let events = Mutex::new(Vec::new());
+    {
+        let mut o = Observable::new(0);
+        o.add_pre_hook(|value| events.lock().push(("pre", *value)));
+        o.add_post_hook(|value| events.lock().push(("post", *value)));
+        let _ = o.try_mutate(|value| {
+            *value = 1;
+            Err(())
+        });
+        let _ = o.try_mutate(|value| {
+            *value = 2;
+            Ok::<_, ()>(())
+        });
+        assert_eq!(o.version(), 1);
+    }
+    assert_eq!(
+        *events.lock(),
+        vec![("pre", 0), ("post", 1), ("pre", 1), ("post", 2)]
+    );
+}
+
+#[test]
+fn transaction_hooks_are_balanced() {
+    let events = Mutex::new(Vec::new());
+    {
+        let mut o = Observable::new(0);
+        o.add_pre_hook(|value| events.lock().push(("pre", *value)));
+        o.add_post_hook(|value| events.lock().push(("post", *value)));
+        let _ = o.transaction(|value| {
+            *value = 1;
+            Err::<(), _>(())
+        });
+        let _ = o.transaction(|value| {
+            *value = 2;
+            Ok::<_, ()>(())
+        });
+    }
+    assert_eq!(
+        *events.lock(),
+        vec![("pre", 0), ("post", 0), ("pre", 0), ("post", 2)]
+    );
+}
+
+#[test]
+fn dispatch_round_robin_resumes_in_priority_order_after_removal() {
+    let served = Arc::new(Mutex::new(Vec::new()));
+    {
+        let o = Observable::new(0);
+        let subscribe_worker = |name: &'static str, priority: i32| {
+            let served = served.clone();
+            let options = SubscribeOptions {
+                priority,
+                ..Default::default()
+            };
+            o.subscribe_opts(options, move |_| {
+                served.lock().push(name);
+                Response::StaySubscribed
+            })
+        };
+        subscribe_worker("low", 0);
+        subscribe_worker("high", 10);
+        let medium = subscribe_worker("medium", 5);
+        assert!(o.delegate().dispatch_round_robin(1));
+        assert!(o.delegate().dispatch_round_robin(2));
+        o.delegate().unsubscribe(medium);
+        assert!(o.delegate().dispatch_round_robin(3));
+        assert!(o.delegate().dispatch_round_robin(4));
+    }
+    assert_eq!(*served.lock(), vec!["high", "medium", "low", "high"]);
+}
+
+#[test]
+fn observables_compare_by_value() {
+    let mut a = Observable::new(0);
+    let b = Observable::new(42);
+    a.subscribe(|_| Response::StaySubscribed);
+    assert_eq!(a, 0);
+    assert_ne!(a, b);
+    a.mutate(|value| *value = 42);
+    assert_eq!(a, 42);
+    assert_eq!(a, b);
+}
+
+#[test]
+#[allow(clippy::mutable_key_type)] // Subscriptions do not participate in ordering or hashing
+fn observables_can_be_stored_in_ordered_and_hashed_sets() {
+    let values = [30, 10, 20];
+
+    let ordered = values
+        .iter()
+        .map(|value| Observable::new(*value))
+        .collect::<BTreeSet<_>>();
+    assert!(ordered.contains(&Observable::new(20)));
+    assert_eq!(
+        ordered.iter().map(|o| **o).collect::<Vec<_>>(),
+        vec![10, 20, 30]
+    );
+
+    let hashed = values
+        .iter()
+        .map(|value| Observable::new(*value))
+        .collect::<HashSet<_>>();
+    assert!(hashed.contains(&Observable::new(30)));
+    assert!(!hashed.contains(&Observable::new(40)));
+}
+
+#[test]
+fn threshold_subscriptions_only_fire_on_crossings() {
+    let mut rising = Vec::new();
+    let mut falling = Vec::new();
+    {
+        let mut o = Observable::new(50);
+        o.subscribe_threshold(25, CrossDirection::Rising, |value| {
+            rising.push(*value);
+            Response::StaySubscribed
+        });
+        o.subscribe_threshold(25, CrossDirection::Falling, |value| {
+            falling.push(*value);
+            Response::StaySubscribed
+        });
+        for value in [30, 25, 24, 10, 25, 26, 20] {
+            o.mutate(|v| *v = value);
+        }
+    }
+    assert_eq!(rising, vec![25]);
+    assert_eq!(falling, vec![24, 20]);
+}
+
+#[test]
+fn restoring_snapshot_broadcasts_restored_value() {
+    let mut seen_values = Vec::new();
+    {
+        let mut o = Observable::new(vec![1]);
+        let snapshot = o.snapshot();
+        o.subscribe(|new_value| {
+            seen_values.push(new_value.clone());
+            Response::StaySubscribed
+        });
+        o.mutate(|value| value.push(2));
+        o.restore(snapshot);
+        assert_eq!(*o, vec![1]);
+    }
+    assert_eq!(seen_values, vec![vec![1, 2], vec![1]]);
+}
+
+#[test]
+fn snapshots_of_persistent_collections_share_structure() {
+    let mut o = Observable::new((0..10_000).collect::<im::Vector<u32>>());
+    let snapshot = o.snapshot();
+    assert!(snapshot.ptr_eq(&o));
+    o.mutate(|value| value.push_back(10_000));
+    assert_eq!(snapshot.len(), 10_000);
+    o.restore(snapshot.clone());
+    assert!(snapshot.ptr_eq(&o));
+}
+
+#[test]
+fn diff_subscribers_receive_changes_by_index() {
+    let mut diffs = Vec::new();
+    {
+        let mut o = Observable::new(vec![1, 2, 3]);
+        o.subscribe_diff(|diff| {
+            diffs.push(diff.clone());
+            Response::StaySubscribed
+        });
+        o.mutate(|v| v[1] = 20);
+        o.mutate(|_| ());
+        o.mutate(|v| v.truncate(1));
+        o.mutate(|v| v.push(4));
+    }
+    assert_eq!(
+        diffs,
+        vec![
+            CollectionDiff {
+                added: vec![],
+                removed: vec![],
+                changed: vec![(1, 2, 20)],
+            },
+            CollectionDiff {
+                added: vec![],
+                removed: vec![(1, 20), (2, 3)],
+                changed: vec![],
+            },
+            CollectionDiff {
+                added: vec![(1, 4)],
+                removed: vec![],
+                changed: vec![],
+            },
+        ]
+    );
+}
+
+#[test]
+fn diff_subscribers_can_identify_elements_by_key() {
+    let mut diffs = Vec::new();
+    {
+        let mut o = Observable::new(vec![("a", 1), ("b", 2), ("c", 3)]);
+        o.subscribe_diff_by_key(
+            |(name, _)| *name,
+            |diff| {
+                diffs.push(diff.clone());
+                Response::StaySubscribed
+            },
+        );
+        o.mutate(|v| {
+            v.remove(0);
+            v[1].1 = 30;
+            v.push(("d", 4));
+        });
+    }
+    assert_eq!(
+        diffs,
+        vec![CollectionDiff {
+            added: vec![(2, ("d", 4))],
+            removed: vec![(0, ("a", 1))],
+            changed: vec![(1, ("c", 3), ("c", 30))],
+        }]
+    );
+}
+
+#[test]
+fn collection_methods_are_reachable_through_deref() {
+    let mut o = Observable::new(Vec::<u8>::new());
+    assert!(o.is_empty());
+    o.mutate(|value| value.extend([1, 2, 3]));
+    assert_eq!(o.len(), 3);
+    assert!(!o.is_empty());
+}
+
+#[test]
+fn coalesced_subscribers_only_see_latest_value_on_pump() {
+    let mut seen_values = Vec::new();
+    {
+        let mut o = Observable::new(0);
+        let s = o.subscribe_coalesced(|new_value| {
+            seen_values.push(*new_value);
+            Response::StaySubscribed
+        });
+        o.pump();
+        o.mutate(|value| *value = 1);
+        o.mutate(|value| *value = 2);
+        o.pump();
+        o.pump();
+        o.mutate(|value| *value = 3);
+        o.pump();
+        o.unsubscribe(s);
+        o.mutate(|value| *value = 4);
+        o.pump();
+    }
+    assert_eq!(seen_values, vec![2, 3]);
+}
+
+#[test]
+fn sampled_subscribers_see_current_value_on_each_sample() {
+    let mut seen_values = Vec::new();
+    {
+        let mut o = Observable::new(0);
+        let s = o.subscribe_sampled(|new_value| {
+            seen_values.push(*new_value);
+            Response::StaySubscribed
+        });
+        o.sample();
+        o.mutate(|value| *value = 1);
+        o.mutate(|value| *value = 2);
+        o.sample();
+        o.sample();
+        o.unsubscribe(s);
+        o.mutate(|value| *value = 3);
+        o.sample();
+    }
+    assert_eq!(seen_values, vec![0, 2, 2]);
+}
+
+#[test]
+fn cloned_observable_has_same_value_and_no_subscribers() {
+    let o = Observable::new(42);
+    o.subscribe(|_| Response::StaySubscribed);
+    let clone = o.clone();
+    assert_eq!(*clone, 42);
+    assert_eq!(o.delegate().subscriber_count(), 1);
+    assert_eq!(clone.delegate().subscriber_count(), 0);
+}
+
+#[test]
+fn cloned_observable_keeps_version_and_armed_state() {
+    let mut seen_values = Vec::new();
+    {
+        let mut o = Observable::new_disarmed(0);
+        o.mutate(|value| *value = 1);
+        let mut clone = o.clone();
+        assert_eq!(clone.version(), 1);
+        clone.subscribe(|new_value| {
+            seen_values.push(*new_value);
+            Response::StaySubscribed
+        });
+        clone.mutate(|value| *value = 2);
+        clone.arm();
+        clone.mutate(|value| *value = 3);
+    }
+    assert_eq!(seen_values, vec![3]);
+}
+
+#[test]
+fn option_transitions_notify_some_and_none_subscribers() {
+    let mut became_some = Vec::new();
+    let mut became_none = 0;
+    {
+        let mut o = Observable::new(None);
+        o.subscribe_on_some(|value| {
+            became_some.push(*value);
+            Response::StaySubscribed
+        });
+        o.subscribe_on_none(|| {
+            became_none += 1;
+            Response::StaySubscribed
+        });
+        o.mutate(|value| *value = None);
+        o.mutate(|value| *value = Some(1));
+        o.mutate(|value| *value = Some(2));
+        o.mutate(|value| *value = None);
+        o.mutate(|value| *value = None);
+        o.mutate(|value| *value = Some(3));
+    }
+    assert_eq!(became_some, vec![1, 3]);
+    assert_eq!(became_none, 1);
+}
+
+#[test]
+fn mutate_and_check_reports_remaining_subscribers() {
+    let mut o = Observable::new(0);
+    assert!(!o.mutate_and_check(|value| *value += 1));
+    o.subscribe_times(2, |_| {});
+    o.subscribe_coalesced(|_| Response::CancelSubscription);
+    assert!(o.mutate_and_check(|value| *value += 1));
+    assert!(o.mutate_and_check(|value| *value += 1));
+    o.pump();
+    assert!(!o.mutate_and_check(|value| *value += 1));
+}
+
+#[test]
+fn subscribe_try_skips_failed_extractions() {
+    let mut seen_values = Vec::new();
+    {
+        let mut o = Observable::new(0);
+        o.subscribe_try(
+            |value| u8::try_from(*value).ok(),
+            |value| {
+                seen_values.push(*value);
+                Response::StaySubscribed
+            },
+        );
+        o.mutate(|value| *value = 5);
+        o.mutate(|value| *value = 500);
+        o.mutate(|value| *value = 6);
+    }
+    assert_eq!(seen_values, vec![5, 6]);
+}
+
+#[test]
+fn update_returns_new_value() {
+    let mut seen_values = Vec::new();
+    {
+        let mut o = Observable::new(1);
+        o.subscribe(|value| {
+            seen_values.push(*value);
+            Response::StaySubscribed
+        });
+        assert_eq!(*o.update(|value| *value *= 10), 10);
+    }
+    assert_eq!(seen_values, vec![10]);
+}
+
+#[test]
+fn arithmetic_assignment_notifies_subscribers() {
+    let mut seen_values = Vec::new();
+    {
+        let mut o = Observable::new(10);
+        o.subscribe(|value| {
+            seen_values.push(*value);
+            Response::StaySubscribed
+        });
+        o += 5;
+        o -= 3;
+        o *= 4;
+        o /= 6;
+    }
+    assert_eq!(seen_values, vec![15, 12, 48, 8]);
+}
+
+#[test]
+fn failed_try_mutate_does_not_notify() {
+    let mut seen_values = Vec::new();
+    {
+        let mut o = Observable::new(0);
+        o.subscribe(|value| {
+            seen_values.push(*value);
+            Response::StaySubscribed
+        });
+        assert_eq!(
+            o.try_mutate(|value| {
+                *value = 1;
+                Ok::<(), &str>(())
+            }),
+            Ok(())
+        );
+        assert_eq!(
+            o.try_mutate(|value| {
+                *value = 2;
+                Err("invalid")
+            }),
+            Err("invalid")
+        );
+        assert_eq!(*o, 2);
+    }
+    assert_eq!(seen_values, vec![1]);
+}
+
+#[test]
+fn validator_corrects_values_before_notifying() {
+    let mut seen_values = Vec::new();
+    {
+        let mut o = Observable::with_validator(150, |value: &mut u32| *value = (*value).min(100));
+        assert_eq!(*o, 100);
+        o.subscribe(|value| {
+            seen_values.push(*value);
+            Response::StaySubscribed
+        });
+        o.mutate(|value| *value = 40);
+        o.mutate(|value| *value += 80);
+        o += 5;
+        o.set_silently(200);
+        assert_eq!(*o, 100);
+    }
+    assert_eq!(seen_values, vec![40, 100, 100]);
+}
+
+#[test]
+fn exchange_notifies_with_new_value_and_returns_old_value() {
+    let mut seen_values = Vec::new();
+    let old = {
+        let mut o = Observable::new(1);
+        o.subscribe(|value| {
+            seen_values.push(*value);
+            Response::StaySubscribed
+        });
+        o.exchange(2)
+    };
+    assert_eq!(old, 1);
+    assert_eq!(seen_values, vec![2]);
+}
+
+#[test]
+fn disarmed_observable_stays_silent_until_armed() {
+    let mut seen_values = Vec::new();
+    {
+        let mut o = Observable::new_disarmed(0);
+        o.subscribe(|value| {
+            seen_values.push(*value);
+            Response::StaySubscribed
+        });
+        o.mutate(|value| *value = 1);
+        o.notify_current();
+        o.arm();
+        o.mutate(|value| *value = 2);
+    }
+    assert_eq!(seen_values, vec![2]);
+}
+
+#[test]
+fn hooks_run_around_mutations() {
+    let events = Mutex::new(Vec::new());
+    {
+        let mut o = Observable::new(0);
+        o.add_pre_hook(|value| events.lock().push(("pre", *value)));
+        let post = o.add_post_hook(|value| events.lock().push(("post", *value)));
+        o.subscribe(|value| {
+            events.lock().push(("subscriber", *value));
+            Response::StaySubscribed
+        });
+        o.mutate(|value| *value = 1);
+        o.set_silently(2);
+        o.unsubscribe(post);
+        o.mutate(|value| *value = 3);
+    }
+    assert_eq!(
+        *events.lock(),
+        vec![
+            ("pre", 0),
+            ("post", 1),
+            ("subscriber", 1),
+            ("pre", 1),
+            ("post", 2),
+            ("pre", 2),
+            ("subscriber", 3),
+        ]
+    );
+}
+
+#[test]
+fn subscribe_until_satisfied_fires_once() {
+    let mut seen_values = Vec::new();
+    {
+        let mut o = Observable::new(0);
+        o.subscribe_until_satisfied(|value| *value >= 2, |value| seen_values.push(*value));
+        for value in 1..5 {
+            o.mutate(|v| *v = value);
+        }
+        assert_eq!(o.delegate().subscriber_count(), 0);
+    }
+    assert_eq!(seen_values, vec![2]);
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn wait_for_resolves_when_predicate_is_satisfied() {
+    let mut o = Observable::new(5);
+    assert_eq!(o.wait_for(|value| *value > 0).await, Some(5));
+    let negative = o.wait_for(|value| *value < 0);
+    o.mutate(|value| *value -= 10);
+    assert_eq!(negative.await, Some(-5));
+    let never = o.wait_for(|value| *value > 100);
+    drop(o);
+    assert_eq!(never.await, None);
+}
@@ -0,0 +1,39 @@
+use parking_lot::Mutex;
+use std::sync::Arc;
+
+use squeak::{Observable, Response};
+
+#[test]
+fn builder_combines_validator_replay_and_subscribers() {
+    let seen_values = Arc::new(Mutex::new(Vec::new()));
+    let seen_values_clone = seen_values.clone();
+    let (mut o, subscriptions) = Observable::builder(150)
+        .validator(|value: &mut u32| *value = (*value).min(100))
+        .subscribe(move |new_value| {
+            seen_values_clone.lock().push(*new_value);
+            Response::StaySubscribed
+        })
+        .subscribe(|_| Response::CancelSubscription)
+        .replay()
+        .build();
+    assert_eq!(subscriptions.len(), 2);
+    assert_eq!(o.delegate().subscriber_count(), 1);
+    o.mutate(|value| *value += 50);
+    assert_eq!(*seen_values.lock(), vec![100, 100]);
+}
+
+#[test]
+fn builder_subscribers_do_not_replay_by_default() {
+    let seen_values = Arc::new(Mutex::new(Vec::new()));
+    let seen_values_clone = seen_values.clone();
+    let (mut o, mut subscriptions) = Observable::builder(1)
+        .subscribe(move |new_value| {
+            seen_values_clone.lock().push(*new_value);
+            Response::StaySubscribed
+        })
+        .build();
+    o.mutate(|value| *value += 1);
+    o.unsubscribe(subscriptions.remove(0));
+    o.mutate(|value| *value += 1);
+    assert_eq!(*seen_values.lock(), vec![2]);
+}
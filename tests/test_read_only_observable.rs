@@ -0,0 +1,22 @@
+use squeak::{Observable, Response};
+
+#[test]
+fn read_only_view_receives_mutations() {
+    let mut seen_value = 0;
+    {
+        let mut o = Observable::new(0);
+        let subscription = {
+            let view = o.read_only();
+            assert_eq!(view.get(), 0);
+            view.subscribe(|new_value| {
+                seen_value = *new_value;
+                Response::StaySubscribed
+            })
+        };
+        o.mutate(|value| *value = 42);
+        assert_eq!(*o.read_only().peek(), 42);
+        o.read_only().unsubscribe(subscription);
+        o.mutate(|value| *value = 43);
+    }
+    assert_eq!(seen_value, 42);
+}
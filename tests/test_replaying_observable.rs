@@ -0,0 +1,22 @@
+use squeak::{ReplayingObservable, Response};
+
+#[test]
+fn new_subscribers_receive_current_value() {
+    let mut seen_values = Vec::new();
+    let mut one_shot_calls = 0;
+    {
+        let mut o = ReplayingObservable::new(1);
+        o.subscribe(|value| {
+            seen_values.push(*value);
+            Response::StaySubscribed
+        });
+        o.subscribe(|_| {
+            one_shot_calls += 1;
+            Response::CancelSubscription
+        });
+        o.mutate(|value| *value = 2);
+        assert_eq!(o.delegate().subscriber_count(), 1);
+    }
+    assert_eq!(seen_values, vec![1, 2]);
+    assert_eq!(one_shot_calls, 1);
+}
@@ -0,0 +1,38 @@
+use std::rc::Rc;
+
+use squeak::{Response, SharedObservable};
+
+#[test]
+fn shared_observable_broadcasts_new_values() {
+    let mut seen_value = 0;
+    {
+        let o = SharedObservable::new(0);
+        o.subscribe(|new_value| {
+            seen_value = *new_value;
+            Response::StaySubscribed
+        });
+        o.mutate(|value| *value = 42);
+        assert_eq!(o.get(), 42);
+    }
+    assert_eq!(seen_value, 42);
+}
+
+#[test]
+fn shared_observable_can_be_mutated_through_shared_handles() {
+    let o = Rc::new(SharedObservable::new(0));
+    let handles = [o.clone(), o.clone()];
+    for handle in &handles {
+        handle.mutate(|value| *value += 1);
+    }
+    assert_eq!(*o.borrow(), 2);
+}
+
+#[test]
+fn debug_snapshot_reports_value_and_subscriber_count() {
+    let o = SharedObservable::new(0);
+    assert_eq!(o.debug_snapshot(), (0, 0));
+    o.subscribe(|_| Response::StaySubscribed);
+    o.subscribe(|_| Response::CancelSubscription);
+    o.mutate(|value| *value = 42);
+    assert_eq!(o.debug_snapshot(), (42, 1));
+}
@@ -0,0 +1,92 @@
+#![cfg(feature = "tracing")]
+
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+use squeak::{Delegate, Response};
+
+type Records = Arc<Mutex<Vec<(String, Option<u64>)>>>;
+
+#[derive(Default)]
+struct FieldRecorder {
+    message: String,
+    subscription: Option<u64>,
+}
+
+impl Visit for FieldRecorder {
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        if field.name() == "subscription" {
+            self.subscription = Some(value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        if field.name() == "message" {
+            self.message = format!("{value:?}");
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+struct RecordingSubscriber {
+    records: Records,
+}
+
+impl Subscriber for RecordingSubscriber {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut recorder = FieldRecorder::default();
+        span.record(&mut recorder);
+        self.records
+            .lock()
+            .unwrap()
+            .push((span.metadata().name().to_owned(), recorder.subscription));
+        let id = self.records.lock().unwrap().len() as u64;
+        Id::from_u64(id)
+    }
+
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut recorder = FieldRecorder::default();
+        event.record(&mut recorder);
+        self.records
+            .lock()
+            .unwrap()
+            .push((recorder.message, recorder.subscription));
+    }
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+#[test]
+fn emits_subscription_lifecycle() {
+    let subscriber = RecordingSubscriber::default();
+    let records = subscriber.records.clone();
+    tracing::subscriber::with_default(subscriber, || {
+        let d = Delegate::new();
+        let subscription = d.subscribe(|_: &u32| Response::CancelSubscription);
+        d.broadcast(0);
+        let id = Some(subscription.id());
+        assert_eq!(
+            *records.lock().unwrap(),
+            vec![
+                ("subscribed".to_owned(), id),
+                ("broadcast".to_owned(), None),
+                ("invoke".to_owned(), id),
+                ("unsubscribed".to_owned(), id),
+            ]
+        );
+    });
+}
@@ -0,0 +1,53 @@
+use parking_lot::Mutex;
+use std::sync::Arc;
+
+use squeak::{Observable, Response, Transaction};
+
+#[test]
+fn transaction_applies_all_mutations_before_notifying() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let mut x = Observable::new(0);
+    let mut y = Observable::new(String::new());
+    let events_clone = events.clone();
+    x.add_post_hook(move |_| events_clone.lock().push("applied x"));
+    let events_clone = events.clone();
+    y.add_post_hook(move |_| events_clone.lock().push("applied y"));
+    let events_clone = events.clone();
+    x.subscribe(move |_| {
+        events_clone.lock().push("notified x");
+        Response::StaySubscribed
+    });
+    let events_clone = events.clone();
+    y.subscribe(move |_| {
+        events_clone.lock().push("notified y");
+        Response::StaySubscribed
+    });
+    let mut transaction = Transaction::new();
+    transaction.mutate(&mut x, |x| *x = 5);
+    transaction.mutate(&mut y, |y| y.push('a'));
+    transaction.commit();
+    assert_eq!(
+        *events.lock(),
+        vec!["applied x", "applied y", "notified x", "notified y"]
+    );
+    assert_eq!(*x, 5);
+    assert_eq!(*y, "a");
+}
+
+#[test]
+fn dropped_transaction_discards_mutations() {
+    let mut call_count = 0;
+    {
+        let mut x = Observable::new(0);
+        x.subscribe(|_| {
+            call_count += 1;
+            Response::StaySubscribed
+        });
+        {
+            let mut transaction = Transaction::new();
+            transaction.mutate(&mut x, |x| *x = 5);
+        }
+        assert_eq!(*x, 0);
+    }
+    assert_eq!(call_count, 0);
+}
@@ -0,0 +1,41 @@
+use std::rc::Rc;
+
+use squeak::{Delegate, Response, WeakDelegate};
+
+#[test]
+fn weak_delegate_subscribes_while_delegate_is_alive() {
+    let d = Rc::new(Delegate::new());
+    let weak = Delegate::downgrade(&d);
+    let subscription = weak.subscribe(|_: &u32| Response::StaySubscribed);
+    assert!(subscription.is_some());
+    assert_eq!(d.subscriber_count(), 1);
+    weak.unsubscribe(subscription.unwrap());
+    assert_eq!(d.subscriber_count(), 0);
+    drop(d);
+    assert!(weak.upgrade().is_none());
+    assert!(weak.subscribe(|_| Response::StaySubscribed).is_none());
+}
+
+#[test]
+fn weak_delegate_does_not_keep_owner_alive() {
+    struct Child {
+        parent_events: WeakDelegate<'static, u32>,
+    }
+    struct Parent {
+        events: Rc<Delegate<'static, u32>>,
+        _children: Vec<Child>,
+    }
+    let events = Rc::new(Delegate::new());
+    let child = Child {
+        parent_events: Delegate::downgrade(&events),
+    };
+    child.parent_events.subscribe(|_| Response::StaySubscribed);
+    let weak = child.parent_events.clone();
+    let parent = Parent {
+        events,
+        _children: vec![child],
+    };
+    assert_eq!(parent.events.subscriber_count(), 1);
+    drop(parent);
+    assert!(weak.upgrade().is_none());
+}
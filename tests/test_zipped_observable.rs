@@ -0,0 +1,41 @@
+use squeak::{Observable, Response};
+
+#[test]
+fn zipped_observable_notifies_when_either_value_changes() {
+    let mut seen_values = Vec::new();
+    {
+        let mut zipped = Observable::new(0).zip(Observable::new('a'));
+        zipped.subscribe(|number, letter| {
+            seen_values.push((*number, *letter));
+            Response::StaySubscribed
+        });
+        zipped.mutate_first(|number| *number = 1);
+        zipped.mutate_second(|letter| *letter = 'b');
+    }
+    assert_eq!(seen_values, vec![(1, 'a'), (1, 'b')]);
+}
+
+#[test]
+fn zipping_preserves_existing_subscriptions() {
+    let mut first_calls = 0;
+    let mut second_calls = 0;
+    {
+        let first = Observable::new(0);
+        let second = Observable::new(0);
+        first.subscribe(|_| {
+            first_calls += 1;
+            Response::StaySubscribed
+        });
+        second.subscribe(|_| {
+            second_calls += 1;
+            Response::StaySubscribed
+        });
+        let mut zipped = first.zip(second);
+        zipped.mutate_first(|value| *value += 1);
+        zipped.mutate_first(|value| *value += 1);
+        let (_, mut second) = zipped.unzip();
+        second.mutate(|value| *value += 1);
+    }
+    assert_eq!(first_calls, 2);
+    assert_eq!(second_calls, 1);
+}